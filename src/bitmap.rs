@@ -0,0 +1,168 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+const WORD_BITS: usize = 64;
+
+//Bitmap packs bools into u64 words, bit i lives in word i/64 at position i%64
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct Bitmap {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl Bitmap {
+    pub fn new() -> Bitmap {
+        Bitmap::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Bitmap {
+        Bitmap {
+            words: Vec::with_capacity(capacity.div_ceil(WORD_BITS)),
+            len: 0,
+        }
+    }
+
+    pub fn push(&mut self, value: bool) {
+        let bit = self.len % WORD_BITS;
+        if bit == 0 {
+            self.words.push(0);
+        }
+        if value {
+            *self.words.last_mut().unwrap() |= 1 << bit;
+        }
+        self.len += 1;
+    }
+
+    pub fn get(&self, idx: usize) -> bool {
+        assert!(
+            idx < self.len,
+            "bitmap index {} out of bounds {}",
+            idx,
+            self.len
+        );
+        self.words[idx / WORD_BITS] & (1 << (idx % WORD_BITS)) != 0
+    }
+
+    pub fn set(&mut self, idx: usize, value: bool) {
+        assert!(
+            idx < self.len,
+            "bitmap index {} out of bounds {}",
+            idx,
+            self.len
+        );
+        let word = &mut self.words[idx / WORD_BITS];
+        if value {
+            *word |= 1 << (idx % WORD_BITS);
+        } else {
+            *word &= !(1 << (idx % WORD_BITS));
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    //keep the first len bits, the unused bits of the last word are cleared
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+        self.words.truncate(len.div_ceil(WORD_BITS));
+        let bit = len % WORD_BITS;
+        if bit > 0 {
+            *self.words.last_mut().unwrap() &= (1 << bit) - 1;
+        }
+        self.len = len;
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        let needed = (self.len + additional).div_ceil(WORD_BITS);
+        self.words.reserve(needed.saturating_sub(self.words.len()));
+    }
+
+    //number of set bits, unused bits of the last word are always zero
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn count_zeros(&self) -> usize {
+        self.len - self.count_ones()
+    }
+
+    pub fn extend(&mut self, other: &Bitmap) {
+        self.reserve(other.len());
+        other.iter().for_each(|b| self.push(b));
+    }
+
+    //bits at indices, in the order given
+    pub fn take(&self, indices: &[usize]) -> Bitmap {
+        indices.iter().map(|i| self.get(*i)).collect()
+    }
+
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(move |i| self.get(i))
+    }
+}
+
+impl FromIterator<bool> for Bitmap {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut bm = Bitmap::new();
+        iter.into_iter().for_each(|b| bm.push(b));
+        bm
+    }
+}
+
+impl fmt::Display for Bitmap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for b in self.iter() {
+            write!(f, "{}", b as u8)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_get_set() {
+        let mut bm = Bitmap::new();
+        for i in 0..130 {
+            bm.push(i % 3 == 0);
+        }
+        assert_eq!(bm.len(), 130);
+        assert_eq!(bm.words().len(), 3);
+        assert!(bm.get(0) && !bm.get(1) && bm.get(129));
+        assert_eq!(bm.count_ones(), 44);
+
+        bm.set(1, true);
+        bm.set(129, false);
+        assert!(bm.get(1) && !bm.get(129));
+        assert_eq!(bm.count_ones(), 44);
+        assert_eq!(bm.count_zeros(), 86);
+        assert_eq!(bm.take(&[129, 1, 3]).to_string(), "011");
+
+        bm.truncate(65);
+        assert_eq!(bm.len(), 65);
+        assert_eq!(bm.words().len(), 2);
+        assert_eq!(bm.count_ones(), 23);
+        bm.push(false);
+        assert!(!bm.get(65));
+    }
+
+    #[test]
+    fn collect_and_display() {
+        let bm: Bitmap = vec![true, false, true].into_iter().collect();
+        assert_eq!(bm.to_string(), "101");
+        assert_eq!(bm.iter().collect::<Vec<_>>(), vec![true, false, true]);
+    }
+}
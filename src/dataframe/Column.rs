@@ -1,6 +1,22 @@
+pub use super::binary::{BinaryEncoding, ColBinary, ColBinaryNullable};
+pub use super::cast::CastOptions;
+pub use super::categorical::ColCategorical;
+pub use super::constraints::Constraint;
+use super::constraints::UniqueSeen;
+pub use super::decimal::{ColDecimal, ColDecimalNullable, Decimal, RoundingMode};
+pub use super::nested::{ColList, ColStruct};
+use super::sort::{self, NullPlacement, SortOrder};
+pub use super::temporal::{
+    ColDate, ColDateNullable, ColDatetime, ColDatetimeNullable, ColDuration, ColDurationNullable,
+    TemporalArgs, TimeUnit,
+};
+use super::{decimal, temporal};
+use crate::bitmap::Bitmap;
 use crate::stringpool;
 use std::any::Any;
 use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
 use std::fmt;
 
 //use super::Result;
@@ -12,6 +28,9 @@ type Result<Column> = std::result::Result<Column, Box<dyn error::Error>>;
 pub enum ColErrorcode {
     ParseDataType,
     SchemaSyntax,
+    ParseValue,
+    DtypeMismatch,
+    Cast,
 }
 
 #[derive(Debug, Clone)]
@@ -33,6 +52,15 @@ impl fmt::Display for ColError {
             ColErrorcode::SchemaSyntax => {
                 write!(f, "ColError: 'SchemaSyntax' \"{}\"", self.error_msg)?;
             }
+            ColErrorcode::ParseValue => {
+                write!(f, "ColError: 'ParseValue' \"{}\"", self.error_msg)?;
+            }
+            ColErrorcode::DtypeMismatch => {
+                write!(f, "ColError: 'DtypeMismatch' \"{}\"", self.error_msg)?;
+            }
+            ColErrorcode::Cast => {
+                write!(f, "ColError: 'Cast' \"{}\"", self.error_msg)?;
+            }
         }
         Ok(())
     }
@@ -40,12 +68,61 @@ impl fmt::Display for ColError {
 impl error::Error for ColError {}
 
 //Column has optional name, a data-type flag and dynamic trait type VectorData
+//metadata holds free-form key/values like units or descriptions
 use serde::{Deserialize, Serialize};
-#[derive(serde::Serialize, Deserialize)]
+#[derive(Debug, serde::Serialize, Deserialize)]
 pub struct Column {
     pub name: Option<String>,
     dtype: Dtype,
     pub data: Box<dyn VectorData>,
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+    #[serde(default)]
+    pub options: CellOptions,
+    #[serde(default)]
+    pub constraints: Vec<Constraint>,
+    //values of the rows checked by unique constraints so far, rebuilt when missing
+    #[serde(skip)]
+    pub(crate) unique_seen: Option<UniqueSeen>,
+}
+
+//CellOptions rewrite text cells before parsing, null tokens become empty cells
+//and empty cells become the default
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CellOptions {
+    pub null_tokens: Vec<String>,
+    pub default: Option<String>,
+}
+
+impl CellOptions {
+    pub fn is_empty(&self) -> bool {
+        self.null_tokens.is_empty() && self.default.is_none()
+    }
+
+    pub fn apply<'a>(&'a self, x: &'a str) -> &'a str {
+        let trimmed = x.trim();
+        let x = if self.null_tokens.iter().any(|t| t == trimmed) {
+            ""
+        } else {
+            x
+        };
+        match &self.default {
+            Some(default) if x.trim().is_empty() => default,
+            _ => x,
+        }
+    }
+
+    //options as in schema brackets, e.g. null=NA|-;default=0
+    pub fn to_args(&self) -> String {
+        let mut items = Vec::new();
+        if !self.null_tokens.is_empty() {
+            items.push(format!("null={}", self.null_tokens.join("|")));
+        }
+        if let Some(default) = &self.default {
+            items.push(format!("default={}", default));
+        }
+        items.join(";")
+    }
 }
 impl Column {
     pub fn new(name: Option<String>, dtype: Dtype) -> Column {
@@ -56,16 +133,329 @@ impl Column {
             Dtype::ColDoubleNullable => Box::new(ColDoubleNullable::default()),
             Dtype::ColString => Box::new(ColString::default()),
             Dtype::ColStringPool => Box::new(ColStringPool::default()),
+            Dtype::ColStringNullable => Box::new(ColStringNullable::default()),
+            Dtype::ColStringPoolNullable => Box::new(ColStringPoolNullable::default()),
+            Dtype::ColBool => Box::new(ColBool::default()),
+            Dtype::ColBoolNullable => Box::new(ColBoolNullable::default()),
+            Dtype::ColI64 => Box::new(ColI64::default()),
+            Dtype::ColU32 => Box::new(ColU32::default()),
+            Dtype::ColU64 => Box::new(ColU64::default()),
+            Dtype::ColF64 => Box::new(ColF64::default()),
+            Dtype::ColI64Nullable => Box::new(ColI64Nullable::default()),
+            Dtype::ColU32Nullable => Box::new(ColU32Nullable::default()),
+            Dtype::ColU64Nullable => Box::new(ColU64Nullable::default()),
+            Dtype::ColF64Nullable => Box::new(ColF64Nullable::default()),
+            Dtype::ColDate => Box::new(ColDate::default()),
+            Dtype::ColDateNullable => Box::new(ColDateNullable::default()),
+            Dtype::ColDatetime(unit) => Box::new(ColDatetime::new(unit, TemporalArgs::default())),
+            Dtype::ColDatetimeNullable(unit) => {
+                Box::new(ColDatetimeNullable::new(unit, TemporalArgs::default()))
+            }
+            Dtype::ColDuration(unit) => Box::new(ColDuration::new(unit)),
+            Dtype::ColDurationNullable(unit) => Box::new(ColDurationNullable::new(unit)),
+            Dtype::ColCategorical => Box::new(ColCategorical::default()),
+            Dtype::ColDecimal(p, s) => Box::new(ColDecimal::new(p, s)),
+            Dtype::ColDecimalNullable(p, s) => Box::new(ColDecimalNullable::new(p, s)),
+            Dtype::ColList => Box::new(ColList::default()),
+            Dtype::ColStruct => Box::new(ColStruct::default()),
+            Dtype::ColBinary => Box::new(ColBinary::default()),
+            Dtype::ColBinaryNullable => Box::new(ColBinaryNullable::default()),
+            Dtype::ColCategoricalNullable => Box::new(ColCategorical::new().with_nulls()),
+            Dtype::ColListNullable => Box::new(ColList::default().with_nulls()),
+            Dtype::ColStructNullable => Box::new(ColStruct {
+                fields: Vec::new(),
+                validity: Some(Bitmap::new()),
+            }),
+        };
+        Column {
+            name,
+            dtype,
+            data,
+            metadata: BTreeMap::new(),
+            options: CellOptions::default(),
+            constraints: Vec::new(),
+            unique_seen: None,
+        }
+    }
+
+    //column around existing data, the dtype is taken from the data
+    pub fn from_data(name: Option<String>, data: Box<dyn VectorData>) -> Column {
+        Column {
+            name,
+            dtype: data.dtype(),
+            data,
+            metadata: BTreeMap::new(),
+            options: CellOptions::default(),
+            constraints: Vec::new(),
+            unique_seen: None,
+        }
+    }
+
+    //the rows at indices, name, metadata, options and constraints are kept
+    pub fn take(&self, indices: &[usize]) -> Column {
+        Column {
+            name: self.name.clone(),
+            dtype: self.dtype,
+            data: self.data.take(indices),
+            metadata: self.metadata.clone(),
+            options: self.options.clone(),
+            constraints: self.constraints.clone(),
+            unique_seen: None,
+        }
+    }
+
+    //other data with the name, metadata, options and constraints of self, as in take
+    pub(crate) fn with_data(&self, data: Box<dyn VectorData>) -> Column {
+        Column {
+            name: self.name.clone(),
+            dtype: data.dtype(),
+            data,
+            metadata: self.metadata.clone(),
+            options: self.options.clone(),
+            constraints: self.constraints.clone(),
+            unique_seen: None,
+        }
+    }
+
+    //column from a dtype descriptor of the schema grammar, e.g. int, datetime[%Y] or list[int]
+    //a ? after the type name or the brackets makes it nullable, e.g. int? or date[%d.%m.%Y]?
+    pub fn from_descr(name: Option<String>, descr: &str, width: AliasWidth) -> Result<Column> {
+        let descr = descr.trim();
+        let (descr, suffixed) = match descr.strip_suffix('?') {
+            Some(x) => (x, true),
+            None => (descr, false),
         };
-        Column { name, dtype, data }
+        let (type_name, type_args) = split_type_descr(descr);
+        let (type_name, nullable) = match type_name.strip_suffix('?') {
+            Some(x) if !suffixed => (x.trim_end(), true),
+            _ => (type_name, suffixed),
+        };
+        let mut dtype = Dtype::from_str_with_width(&type_name.to_lowercase(), width)?;
+        if nullable {
+            dtype = dtype.nullable();
+        }
+        match dtype {
+            Dtype::ColList | Dtype::ColListNullable if !type_args.is_empty() => {
+                let list = ColList::from_args(type_args, width)?;
+                let list = if dtype.is_nullable() {
+                    list.with_nulls()
+                } else {
+                    list
+                };
+                Ok(Column::from_data(name, Box::new(list)))
+            }
+            Dtype::ColStruct | Dtype::ColStructNullable => {
+                let fields = ColStruct::from_args(type_args, width)?;
+                let fields = if dtype.is_nullable() {
+                    fields.with_nulls()?
+                } else {
+                    fields
+                };
+                Ok(Column::from_data(name, Box::new(fields)))
+            }
+            _ => Column::new_with_args(name, dtype, type_args),
+        }
+    }
+
+    //as new, with the bracket arguments of the schema, e.g. the format of datetime[%Y-%m-%d]
+    pub fn new_with_args(name: Option<String>, dtype: Dtype, args: &str) -> Result<Column> {
+        let data: Box<dyn VectorData> = match dtype {
+            Dtype::ColBinary | Dtype::ColBinaryNullable if !args.trim().is_empty() => {
+                let encoding = BinaryEncoding::from_str_to_res(args)?;
+                if dtype.is_nullable() {
+                    Box::new(ColBinaryNullable::new(encoding))
+                } else {
+                    Box::new(ColBinary::new(encoding))
+                }
+            }
+            Dtype::ColBool | Dtype::ColBoolNullable if !args.trim().is_empty() => {
+                let tokens = BoolTokens::from_args(args)?;
+                if dtype.is_nullable() {
+                    Box::new(ColBoolNullable::with_tokens(tokens))
+                } else {
+                    Box::new(ColBool::with_tokens(tokens))
+                }
+            }
+            Dtype::ColDate | Dtype::ColDateNullable => {
+                let t_args = TemporalArgs::parse(args)?;
+                if t_args.unit.is_some() || t_args.tz.is_some() {
+                    Err(ColError {
+                        errorcode: ColErrorcode::SchemaSyntax,
+                        error_msg: format!("date only takes a format, got \"{}\"", args),
+                    })?;
+                }
+                if dtype == Dtype::ColDate {
+                    Box::new(ColDate::new(t_args))
+                } else {
+                    Box::new(ColDateNullable::new(t_args))
+                }
+            }
+            Dtype::ColDatetime(unit) => {
+                let t_args = TemporalArgs::parse(args)?;
+                Box::new(ColDatetime::new(t_args.unit.unwrap_or(unit), t_args))
+            }
+            Dtype::ColDatetimeNullable(unit) => {
+                let t_args = TemporalArgs::parse(args)?;
+                Box::new(ColDatetimeNullable::new(
+                    t_args.unit.unwrap_or(unit),
+                    t_args,
+                ))
+            }
+            Dtype::ColDuration(unit) | Dtype::ColDurationNullable(unit) => {
+                let t_args = TemporalArgs::parse(args)?;
+                if t_args.format.is_some() || t_args.tz.is_some() {
+                    Err(ColError {
+                        errorcode: ColErrorcode::SchemaSyntax,
+                        error_msg: format!("duration only takes a unit, got \"{}\"", args),
+                    })?;
+                }
+                let unit = t_args.unit.unwrap_or(unit);
+                if dtype.is_nullable() {
+                    Box::new(ColDurationNullable::new(unit))
+                } else {
+                    Box::new(ColDuration::new(unit))
+                }
+            }
+            Dtype::ColList | Dtype::ColListNullable if !args.trim().is_empty() => {
+                let list = ColList::from_args(args, AliasWidth::default())?;
+                if dtype.is_nullable() {
+                    Box::new(list.with_nulls())
+                } else {
+                    Box::new(list)
+                }
+            }
+            Dtype::ColStruct | Dtype::ColStructNullable => {
+                let fields = ColStruct::from_args(args, AliasWidth::default())?;
+                if dtype.is_nullable() {
+                    Box::new(fields.with_nulls()?)
+                } else {
+                    Box::new(fields)
+                }
+            }
+            Dtype::ColDecimal(..) | Dtype::ColDecimalNullable(..) if !args.trim().is_empty() => {
+                let (p, s) = decimal::parse_decimal_args(args)?;
+                if dtype.is_nullable() {
+                    Box::new(ColDecimalNullable::new(p, s))
+                } else {
+                    Box::new(ColDecimal::new(p, s))
+                }
+            }
+            _ => {
+                if !args.trim().is_empty() {
+                    Err(ColError {
+                        errorcode: ColErrorcode::SchemaSyntax,
+                        error_msg: format!("{} takes no arguments, got \"{}\"", dtype, args),
+                    })?;
+                }
+                return Ok(Column::new(name, dtype));
+            }
+        };
+        Ok(Column::from_data(name, data))
+    }
+
+    pub fn dtype(&self) -> Dtype {
+        self.dtype
+    }
+
+    //push a text cell, null tokens and defaults of the options applied
+    pub fn push_from_str(&mut self, x: &str) -> Result<()> {
+        self.data.push_from_str(self.options.apply(x))
+    }
+
+    //the dtype as in a schema, e.g. datetime[%d.%m.%Y;unit=ms]
+    pub fn type_descr(&self) -> String {
+        type_descr(self.data.as_ref())
+    }
+
+    fn dtype_mismatch(&self, expected: &str) -> Box<dyn error::Error> {
+        Box::new(ColError {
+            errorcode: ColErrorcode::DtypeMismatch,
+            error_msg: format!(
+                "column \"{}\" is {}, not {}",
+                self.name.as_deref().unwrap_or("None"),
+                self.dtype,
+                expected
+            ),
+        })
+    }
+
+    //the data as its concrete column type, e.g. col.get::<ColInt>()
+    pub fn get<T: VectorData + 'static>(&self) -> Result<&T> {
+        self.data.as_any().downcast_ref::<T>().ok_or_else(|| {
+            let type_name = std::any::type_name::<T>();
+            self.dtype_mismatch(type_name.rsplit("::").next().unwrap_or(type_name))
+        })
+    }
+
+    //strings of ColString, ColStringPool or ColCategorical
+    pub fn as_str_iter(&self) -> Result<Box<dyn Iterator<Item = &str> + '_>> {
+        let any = self.data.as_any();
+        if let Some(col) = any.downcast_ref::<ColString>() {
+            Ok(Box::new(col.data.iter().map(|x| &x[..])))
+        } else if let Some(col) = any.downcast_ref::<ColStringPool>() {
+            Ok(Box::new((0..col.data.len()).map(|i| col.data.get_str(i))))
+        } else if let Some(col) = any.downcast_ref::<ColCategorical>() {
+            Ok(Box::new((0..col.len()).map(|i| col.get_str(i))))
+        } else {
+            Err(self.dtype_mismatch("a string"))
+        }
+    }
+
+    //as as_str_iter, nullable strings included
+    pub fn as_str_opt_iter(&self) -> Result<Box<dyn Iterator<Item = Option<&str>> + '_>> {
+        let any = self.data.as_any();
+        if let Some(col) = any.downcast_ref::<ColStringNullable>() {
+            Ok(Box::new((0..col.len()).map(|i| col.get(i))))
+        } else if let Some(col) = any.downcast_ref::<ColStringPoolNullable>() {
+            Ok(Box::new((0..col.len()).map(|i| col.get(i))))
+        } else {
+            Ok(Box::new(self.as_str_iter()?.map(Some)))
+        }
     }
 }
+
+//as_i32() gives the &[i32] of a ColInt, as_i32_opt() the values of ColInt or ColIntNullable
+macro_rules! typed_accessors {
+    ($($as_t:ident, $as_t_opt:ident, $col:ident, $col_nullable:ident, $t:ty);* $(;)?) => {
+        impl Column {
+            $(
+                pub fn $as_t(&self) -> Result<&[$t]> {
+                    Ok(&self.get::<$col>()?.data)
+                }
+
+                pub fn $as_t_opt(&self) -> Result<Box<dyn Iterator<Item = Option<$t>> + '_>> {
+                    let any = self.data.as_any();
+                    if let Some(col) = any.downcast_ref::<$col_nullable>() {
+                        Ok(Box::new(col.iter()))
+                    } else if let Some(col) = any.downcast_ref::<$col>() {
+                        Ok(Box::new(col.data.iter().map(|x| Some(*x))))
+                    } else {
+                        Err(self.dtype_mismatch(stringify!($t)))
+                    }
+                }
+            )*
+        }
+    };
+}
+
+typed_accessors!(
+    as_i32, as_i32_opt, ColInt, ColIntNullable, i32;
+    as_f32, as_f32_opt, ColDouble, ColDoubleNullable, f32;
+    as_i64, as_i64_opt, ColI64, ColI64Nullable, i64;
+    as_u32, as_u32_opt, ColU32, ColU32Nullable, u32;
+    as_u64, as_u64_opt, ColU64, ColU64Nullable, u64;
+    as_f64, as_f64_opt, ColF64, ColF64Nullable, f64;
+);
 impl Clone for Column {
     fn clone(&self) -> Self {
         Column {
             name: self.name.clone(),
             dtype: self.dtype,
             data: self.data.boxed_clone(),
+            metadata: self.metadata.clone(),
+            options: self.options.clone(),
+            constraints: self.constraints.clone(),
+            unique_seen: self.unique_seen.clone(),
         }
     }
 }
@@ -78,15 +468,88 @@ pub trait VectorData {
     fn to_string(&self) -> String;
     fn as_any(&self) -> &dyn Any;
     fn reserve(&mut self, additional: usize);
+    //keep the first len rows, like Vec::truncate
+    fn truncate(&mut self, len: usize);
     fn dtype(&self) -> Dtype;
     fn boxed_clone(&self) -> Box<dyn VectorData>;
     fn len(&self) -> usize;
+    fn get_value(&self, idx: usize) -> Value<'_>;
+    fn take(&self, indices: &[usize]) -> Box<dyn VectorData>;
+    //rows where the mask is set, mask has one bit per row. built on take, so string pools
+    //only keep the strings of the remaining rows
+    fn filter(&self, mask: &Bitmap) -> Box<dyn VectorData> {
+        let indices: Vec<usize> = (0..mask.len()).filter(|&i| mask.get(i)).collect();
+        self.take(&indices)
+    }
+    //row order that sorts the column, stable
+    fn argsort(&self, order: SortOrder, nulls: NullPlacement) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..self.len()).collect();
+        indices
+            .sort_by(|&i, &j| sort::compare(&self.get_value(i), &self.get_value(j), order, nulls));
+        indices
+    }
+    //append the rows of other, which has to be of the same dtype
+    fn extend(&mut self, other: &dyn VectorData) -> Result<()>;
+
+    //bit i is set if row i holds a value, None for columns that cannot hold nulls
+    fn validity(&self) -> Option<&Bitmap> {
+        None
+    }
+    fn null_count(&self) -> usize {
+        self.validity().map_or(0, |v| v.count_zeros())
+    }
+    fn is_null(&self, idx: usize) -> bool {
+        self.validity().is_some_and(|v| !v.get(idx))
+    }
+    //the bracket arguments that rebuild this column from a schema, e.g. the datetime format
+    fn type_args(&self) -> String {
+        String::new()
+    }
 }
 impl fmt::Display for dyn VectorData {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.to_string())
     }
 }
+impl<'a> fmt::Debug for dyn VectorData + 'a {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}[{}]", self.dtype(), self.to_string())
+    }
+}
+
+//other as the concrete column type of a column of dtype, for extend
+pub(crate) fn extend_source<T: VectorData + 'static>(
+    other: &dyn VectorData,
+    dtype: Dtype,
+) -> Result<&T> {
+    let err = || ColError {
+        errorcode: ColErrorcode::DtypeMismatch,
+        error_msg: format!("cannot extend {} by {}", dtype, other.dtype()),
+    };
+    if other.dtype() != dtype {
+        Err(err())?;
+    }
+    Ok(other.as_any().downcast_ref::<T>().ok_or_else(err)?)
+}
+
+//dtype descriptor of data, type_args in brackets if there are any
+pub fn type_descr(data: &dyn VectorData) -> String {
+    let args = data.type_args();
+    if args.is_empty() {
+        data.dtype().type_name().to_string()
+    } else {
+        format!("{}[{}]", data.dtype().type_name(), args)
+    }
+}
+
+//split a dtype descriptor like datetime[%H:%M] into its name and the text inside the brackets
+pub fn split_type_descr(descr: &str) -> (&str, &str) {
+    let descr = descr.trim();
+    match descr.split_once('[') {
+        Some((t_name, rest)) if rest.ends_with(']') => (t_name.trim(), &rest[..rest.len() - 1]),
+        _ => (descr, ""),
+    }
+}
 
 //Dtype is the column flag of data type.
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
@@ -97,6 +560,34 @@ pub enum Dtype {
     ColDoubleNullable,
     ColString,
     ColStringPool, //stringpool crate
+    ColStringNullable,
+    ColStringPoolNullable,
+    ColBool,
+    ColBoolNullable,
+    ColI64,
+    ColU32,
+    ColU64,
+    ColF64,
+    ColI64Nullable,
+    ColU32Nullable,
+    ColU64Nullable,
+    ColF64Nullable,
+    ColDate,
+    ColDateNullable,
+    ColDatetime(TimeUnit),
+    ColDatetimeNullable(TimeUnit),
+    ColDuration(TimeUnit),
+    ColDurationNullable(TimeUnit),
+    ColCategorical,
+    ColDecimal(u8, u8), //precision, scale
+    ColDecimalNullable(u8, u8),
+    ColList,
+    ColStruct,
+    ColBinary,
+    ColBinaryNullable,
+    ColCategoricalNullable,
+    ColListNullable,
+    ColStructNullable,
 }
 impl fmt::Display for Dtype {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -107,20 +598,199 @@ impl fmt::Display for Dtype {
             Dtype::ColDoubleNullable => write!(f, "DType: ColDoubleNullable"),
             Dtype::ColString => write!(f, "DType: ColString"),
             Dtype::ColStringPool => write!(f, "DType: ColStringPool"),
+            Dtype::ColStringNullable => write!(f, "DType: ColStringNullable"),
+            Dtype::ColStringPoolNullable => write!(f, "DType: ColStringPoolNullable"),
+            Dtype::ColBool => write!(f, "DType: ColBool"),
+            Dtype::ColBoolNullable => write!(f, "DType: ColBoolNullable"),
+            Dtype::ColI64 => write!(f, "DType: ColI64"),
+            Dtype::ColU32 => write!(f, "DType: ColU32"),
+            Dtype::ColU64 => write!(f, "DType: ColU64"),
+            Dtype::ColF64 => write!(f, "DType: ColF64"),
+            Dtype::ColI64Nullable => write!(f, "DType: ColI64Nullable"),
+            Dtype::ColU32Nullable => write!(f, "DType: ColU32Nullable"),
+            Dtype::ColU64Nullable => write!(f, "DType: ColU64Nullable"),
+            Dtype::ColF64Nullable => write!(f, "DType: ColF64Nullable"),
+            Dtype::ColDate => write!(f, "DType: ColDate"),
+            Dtype::ColDateNullable => write!(f, "DType: ColDateNullable"),
+            Dtype::ColDatetime(unit) => write!(f, "DType: ColDatetime[{}]", unit),
+            Dtype::ColDatetimeNullable(unit) => write!(f, "DType: ColDatetimeNullable[{}]", unit),
+            Dtype::ColDuration(unit) => write!(f, "DType: ColDuration[{}]", unit),
+            Dtype::ColDurationNullable(unit) => write!(f, "DType: ColDurationNullable[{}]", unit),
+            Dtype::ColCategorical => write!(f, "DType: ColCategorical"),
+            Dtype::ColDecimal(p, s) => write!(f, "DType: ColDecimal[{},{}]", p, s),
+            Dtype::ColDecimalNullable(p, s) => write!(f, "DType: ColDecimalNullable[{},{}]", p, s),
+            Dtype::ColList => write!(f, "DType: ColList"),
+            Dtype::ColStruct => write!(f, "DType: ColStruct"),
+            Dtype::ColBinary => write!(f, "DType: ColBinary"),
+            Dtype::ColBinaryNullable => write!(f, "DType: ColBinaryNullable"),
+            Dtype::ColCategoricalNullable => write!(f, "DType: ColCategoricalNullable"),
+            Dtype::ColListNullable => write!(f, "DType: ColListNullable"),
+            Dtype::ColStructNullable => write!(f, "DType: ColStructNullable"),
         }
     }
 }
 
 impl Dtype {
+    //name in the schema grammar, arguments like the datetime unit are left to type_args
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Dtype::ColInt => "int",
+            Dtype::ColDouble => "double",
+            Dtype::ColIntNullable => "intNullable",
+            Dtype::ColDoubleNullable => "doubleNullable",
+            Dtype::ColString => "string",
+            Dtype::ColStringPool => "stringPool",
+            Dtype::ColStringNullable => "stringNullable",
+            Dtype::ColStringPoolNullable => "stringPoolNullable",
+            Dtype::ColBool => "bool",
+            Dtype::ColBoolNullable => "boolNullable",
+            Dtype::ColI64 => "i64",
+            Dtype::ColU32 => "u32",
+            Dtype::ColU64 => "u64",
+            Dtype::ColF64 => "f64",
+            Dtype::ColI64Nullable => "i64Nullable",
+            Dtype::ColU32Nullable => "u32Nullable",
+            Dtype::ColU64Nullable => "u64Nullable",
+            Dtype::ColF64Nullable => "f64Nullable",
+            Dtype::ColDate => "date",
+            Dtype::ColDateNullable => "dateNullable",
+            Dtype::ColDatetime(_) => "datetime",
+            Dtype::ColDatetimeNullable(_) => "datetimeNullable",
+            Dtype::ColDuration(_) => "duration",
+            Dtype::ColDurationNullable(_) => "durationNullable",
+            Dtype::ColCategorical => "categorical",
+            Dtype::ColDecimal(..) => "decimal",
+            Dtype::ColDecimalNullable(..) => "decimalNullable",
+            Dtype::ColList => "list",
+            Dtype::ColStruct => "struct",
+            Dtype::ColBinary => "binary",
+            Dtype::ColBinaryNullable => "binaryNullable",
+            Dtype::ColCategoricalNullable => "categoricalNullable",
+            Dtype::ColListNullable => "listNullable",
+            Dtype::ColStructNullable => "structNullable",
+        }
+    }
+
+    pub fn is_nullable(&self) -> bool {
+        matches!(
+            self,
+            Dtype::ColIntNullable
+                | Dtype::ColDoubleNullable
+                | Dtype::ColStringNullable
+                | Dtype::ColStringPoolNullable
+                | Dtype::ColBoolNullable
+                | Dtype::ColI64Nullable
+                | Dtype::ColU32Nullable
+                | Dtype::ColU64Nullable
+                | Dtype::ColF64Nullable
+                | Dtype::ColDateNullable
+                | Dtype::ColDatetimeNullable(_)
+                | Dtype::ColDurationNullable(_)
+                | Dtype::ColDecimalNullable(..)
+                | Dtype::ColBinaryNullable
+                | Dtype::ColCategoricalNullable
+                | Dtype::ColListNullable
+                | Dtype::ColStructNullable
+        )
+    }
+
+    //the nullable variant of a dtype, nullable dtypes stay as they are
+    pub fn nullable(self) -> Dtype {
+        match self {
+            Dtype::ColInt => Dtype::ColIntNullable,
+            Dtype::ColDouble => Dtype::ColDoubleNullable,
+            Dtype::ColString => Dtype::ColStringNullable,
+            Dtype::ColStringPool => Dtype::ColStringPoolNullable,
+            Dtype::ColBool => Dtype::ColBoolNullable,
+            Dtype::ColI64 => Dtype::ColI64Nullable,
+            Dtype::ColU32 => Dtype::ColU32Nullable,
+            Dtype::ColU64 => Dtype::ColU64Nullable,
+            Dtype::ColF64 => Dtype::ColF64Nullable,
+            Dtype::ColDate => Dtype::ColDateNullable,
+            Dtype::ColDatetime(unit) => Dtype::ColDatetimeNullable(unit),
+            Dtype::ColDuration(unit) => Dtype::ColDurationNullable(unit),
+            Dtype::ColDecimal(p, s) => Dtype::ColDecimalNullable(p, s),
+            Dtype::ColBinary => Dtype::ColBinaryNullable,
+            Dtype::ColCategorical => Dtype::ColCategoricalNullable,
+            Dtype::ColList => Dtype::ColListNullable,
+            Dtype::ColStruct => Dtype::ColStructNullable,
+            other => other,
+        }
+    }
+
+    pub fn non_nullable(self) -> Dtype {
+        match self {
+            Dtype::ColIntNullable => Dtype::ColInt,
+            Dtype::ColDoubleNullable => Dtype::ColDouble,
+            Dtype::ColStringNullable => Dtype::ColString,
+            Dtype::ColStringPoolNullable => Dtype::ColStringPool,
+            Dtype::ColBoolNullable => Dtype::ColBool,
+            Dtype::ColI64Nullable => Dtype::ColI64,
+            Dtype::ColU32Nullable => Dtype::ColU32,
+            Dtype::ColU64Nullable => Dtype::ColU64,
+            Dtype::ColF64Nullable => Dtype::ColF64,
+            Dtype::ColDateNullable => Dtype::ColDate,
+            Dtype::ColDatetimeNullable(unit) => Dtype::ColDatetime(unit),
+            Dtype::ColDurationNullable(unit) => Dtype::ColDuration(unit),
+            Dtype::ColDecimalNullable(p, s) => Dtype::ColDecimal(p, s),
+            Dtype::ColBinaryNullable => Dtype::ColBinary,
+            Dtype::ColCategoricalNullable => Dtype::ColCategorical,
+            Dtype::ColListNullable => Dtype::ColList,
+            Dtype::ColStructNullable => Dtype::ColStruct,
+            other => other,
+        }
+    }
+
     pub fn from_str_to_res(s: &str) -> Result<Dtype> {
+        Dtype::from_str_with_width(s, AliasWidth::default())
+    }
+
+    //as from_str_to_res, but the int and double aliases resolve by width
+    pub fn from_str_with_width(s: &str, width: AliasWidth) -> Result<Dtype> {
         let lc_s = s.to_lowercase();
+        let wide = width == AliasWidth::Wide;
         let new_dtype = match &lc_s[..] {
-            "int" => Dtype::ColInt,
-            "intnullable" => Dtype::ColIntNullable,
-            "double" => Dtype::ColDouble,
-            "doublenullable" => Dtype::ColDoubleNullable,
+            "int" if wide => Dtype::ColI64,
+            "intnullable" if wide => Dtype::ColI64Nullable,
+            "double" if wide => Dtype::ColF64,
+            "doublenullable" if wide => Dtype::ColF64Nullable,
+            "int" | "i32" => Dtype::ColInt,
+            "intnullable" | "i32nullable" => Dtype::ColIntNullable,
+            "double" | "f32" => Dtype::ColDouble,
+            "doublenullable" | "f32nullable" => Dtype::ColDoubleNullable,
+            "i64" => Dtype::ColI64,
+            "u32" => Dtype::ColU32,
+            "u64" => Dtype::ColU64,
+            "f64" => Dtype::ColF64,
+            "i64nullable" => Dtype::ColI64Nullable,
+            "u32nullable" => Dtype::ColU32Nullable,
+            "u64nullable" => Dtype::ColU64Nullable,
+            "f64nullable" => Dtype::ColF64Nullable,
+            "date" => Dtype::ColDate,
+            "datenullable" => Dtype::ColDateNullable,
+            "datetime" => Dtype::ColDatetime(TimeUnit::Microseconds),
+            "datetimenullable" => Dtype::ColDatetimeNullable(TimeUnit::Microseconds),
+            "duration" => Dtype::ColDuration(TimeUnit::Microseconds),
+            "durationnullable" => Dtype::ColDurationNullable(TimeUnit::Microseconds),
+            "categorical" => Dtype::ColCategorical,
+            "categoricalnullable" => Dtype::ColCategoricalNullable,
+            //without [precision,scale] decimals are whole numbers of up to 38 digits
+            "decimal" => Dtype::ColDecimal(decimal::MAX_PRECISION, 0),
+            "decimalnullable" => Dtype::ColDecimalNullable(decimal::MAX_PRECISION, 0),
+            //element and field dtypes are given in brackets, e.g. list[int]
+            "list" => Dtype::ColList,
+            "struct" => Dtype::ColStruct,
+            "listnullable" => Dtype::ColListNullable,
+            "structnullable" => Dtype::ColStructNullable,
+            //hex by default, binary[base64] for base64 text
+            "binary" => Dtype::ColBinary,
+            "binarynullable" => Dtype::ColBinaryNullable,
             "string" => Dtype::ColString,
             "stringpool" => Dtype::ColStringPool,
+            "stringnullable" => Dtype::ColStringNullable,
+            "stringpoolnullable" => Dtype::ColStringPoolNullable,
+            "bool" => Dtype::ColBool,
+            "boolnullable" => Dtype::ColBoolNullable,
             _ => {
                 let err = ColError {
                     errorcode: ColErrorcode::ParseDataType,
@@ -133,6 +803,121 @@ impl Dtype {
     }
 }
 
+//AliasWidth decides if the schema aliases int and double mean 32 or 64 bit types
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum AliasWidth {
+    #[default]
+    Narrow,
+    Wide,
+}
+
+//Value is a borrowed view of one cell, for code handling cells of any column type alike
+#[derive(Clone, Copy, Debug)]
+pub enum Value<'a> {
+    Null,
+    Int(i64),
+    UInt(u64),
+    Double(f64),
+    Str(&'a str),
+    Bool(bool),
+    Date(i32),
+    Datetime(i64, TimeUnit),
+    Duration(i64, TimeUnit),
+    Decimal(Decimal),
+    Binary(&'a [u8]),
+    List(&'a dyn VectorData, usize, usize), //child, start and end of the elements
+    Struct(&'a [Column], usize),            //fields, row
+}
+
+impl PartialEq for Value<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::Null, Value::Null) => true,
+            (Value::Int(a), Value::Int(b)) => a == b,
+            (Value::UInt(a), Value::UInt(b)) => a == b,
+            (Value::Double(a), Value::Double(b)) => a == b,
+            (Value::Str(a), Value::Str(b)) => a == b,
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::Date(a), Value::Date(b)) => a == b,
+            (Value::Datetime(a, ua), Value::Datetime(b, ub)) => a == b && ua == ub,
+            (Value::Duration(a, ua), Value::Duration(b, ub)) => a == b && ua == ub,
+            (Value::Decimal(a), Value::Decimal(b)) => a == b,
+            (Value::Binary(a), Value::Binary(b)) => a == b,
+            (Value::List(a, a0, a1), Value::List(b, b0, b1)) => {
+                a1 - a0 == b1 - b0
+                    && (0..a1 - a0).all(|i| a.get_value(a0 + i) == b.get_value(b0 + i))
+            }
+            (Value::Struct(a, i), Value::Struct(b, j)) => {
+                a.len() == b.len()
+                    && a.iter().zip(b.iter()).all(|(x, y)| {
+                        x.name == y.name && x.data.get_value(*i) == y.data.get_value(*j)
+                    })
+            }
+            _ => false,
+        }
+    }
+}
+
+//cells of the same kind order naturally, nulls, NaN and cells of different kinds do not order
+impl PartialOrd for Value<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Value::Int(a), Value::Int(b)) => a.partial_cmp(b),
+            (Value::UInt(a), Value::UInt(b)) => a.partial_cmp(b),
+            (Value::Double(a), Value::Double(b)) => a.partial_cmp(b),
+            (Value::Str(a), Value::Str(b)) => a.partial_cmp(b),
+            (Value::Bool(a), Value::Bool(b)) => a.partial_cmp(b),
+            (Value::Date(a), Value::Date(b)) => a.partial_cmp(b),
+            (Value::Datetime(a, ua), Value::Datetime(b, ub)) if ua == ub => a.partial_cmp(b),
+            (Value::Duration(a, ua), Value::Duration(b, ub)) if ua == ub => a.partial_cmp(b),
+            (Value::Decimal(a), Value::Decimal(b)) => a.partial_cmp(b),
+            (Value::Binary(a), Value::Binary(b)) => a.partial_cmp(b),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Value<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Null => write!(f, "NA"),
+            Value::Int(x) => write!(f, "{}", x),
+            Value::UInt(x) => write!(f, "{}", x),
+            Value::Double(x) => write!(f, "{}", x),
+            Value::Str(x) => write!(f, "{}", x),
+            Value::Bool(x) => write!(f, "{}", x),
+            Value::Date(x) => {
+                let text = temporal::format_date(*x, None);
+                write!(f, "{}", temporal::or_raw(text, *x as i64))
+            }
+            Value::Datetime(x, unit) => {
+                let text = temporal::format_datetime(*x, None, *unit, None);
+                write!(f, "{}", temporal::or_raw(text, *x))
+            }
+            Value::Duration(x, unit) => write!(f, "{}", temporal::format_duration(*x, *unit)),
+            Value::Decimal(x) => write!(f, "{}", x),
+            Value::Binary(x) => write!(f, "{}", BinaryEncoding::Hex.encode(x)),
+            Value::List(child, start, end) => {
+                write!(f, "[")?;
+                for i in *start..*end {
+                    let sep = if i + 1 < *end { ", " } else { "" };
+                    write!(f, "{}{}", child.get_value(i), sep)?;
+                }
+                write!(f, "]")
+            }
+            Value::Struct(fields, idx) => {
+                write!(f, "{{")?;
+                for (i, field) in fields.iter().enumerate() {
+                    let sep = if i + 1 < fields.len() { ", " } else { "" };
+                    let name = field.name.as_deref().unwrap_or("");
+                    write!(f, "{}: {}{}", name, field.data.get_value(*idx), sep)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
 //all structs that implement VectorData
 
 //vanilla i32 f32 vectors
@@ -151,16 +936,46 @@ pub struct ColDouble {
     pub data: Vec<f32>,
 }
 
-//nullable, vectors of optional i32 and f32
-#[derive(Clone, Debug, PartialEq, PartialOrd, Default, Serialize, Deserialize)]
+//nullable i32 and f32, dense values plus a validity bitmap where a set bit is a value
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
 pub struct ColIntNullable {
-    pub data: Vec<Option<i32>>,
+    pub data: Vec<i32>,
+    pub validity: Bitmap,
 }
 
-#[derive(Clone, Debug, PartialEq, PartialOrd, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
 pub struct ColDoubleNullable {
-    pub data: Vec<Option<f32>>,
+    pub data: Vec<f32>,
+    pub validity: Bitmap,
+}
+
+//push, get and iter for nullable columns with a Vec data buffer and a validity bitmap
+macro_rules! nullable_values {
+    ($col:ident, $t:ty) => {
+        impl $col {
+            pub fn push(&mut self, value: Option<$t>) {
+                self.data.push(value.unwrap_or_default());
+                self.validity.push(value.is_some());
+            }
+
+            pub fn get(&self, idx: usize) -> Option<$t> {
+                if self.validity.get(idx) {
+                    Some(self.data[idx])
+                } else {
+                    None
+                }
+            }
+
+            pub fn iter(&self) -> impl Iterator<Item = Option<$t>> + '_ {
+                (0..self.data.len()).map(move |i| self.get(i))
+            }
+        }
+    };
 }
+pub(crate) use nullable_values;
+
+nullable_values!(ColIntNullable, i32);
+nullable_values!(ColDoubleNullable, f32);
 
 //string vector
 
@@ -174,6 +989,190 @@ pub struct ColStringPool {
     pub data: stringpool::StringPool,
 }
 
+//nullable strings, empty cells are null and hold an empty string in data
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct ColStringNullable {
+    pub data: Vec<String>,
+    pub validity: Bitmap,
+}
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct ColStringPoolNullable {
+    pub data: stringpool::StringPool,
+    pub validity: Bitmap,
+}
+
+impl ColStringNullable {
+    pub fn push(&mut self, value: Option<&str>) {
+        self.data.push(String::from(value.unwrap_or_default()));
+        self.validity.push(value.is_some());
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&str> {
+        if self.validity.get(idx) {
+            Some(&self.data[idx])
+        } else {
+            None
+        }
+    }
+}
+
+impl ColStringPoolNullable {
+    pub fn push(&mut self, value: Option<&str>) {
+        self.data.add_str(value.unwrap_or_default());
+        self.validity.push(value.is_some());
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&str> {
+        if self.validity.get(idx) {
+            Some(self.data.get_str(idx))
+        } else {
+            None
+        }
+    }
+}
+
+//bit-packed bools, tokens decide which strings parse as true or false
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct ColBool {
+    pub data: Bitmap,
+    pub tokens: BoolTokens,
+}
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct ColBoolNullable {
+    pub data: Bitmap,
+    pub validity: Bitmap,
+    pub tokens: BoolTokens,
+}
+
+//tokens recognized as bools, compared case insensitive after trimming
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BoolTokens {
+    pub true_tokens: Vec<String>,
+    pub false_tokens: Vec<String>,
+}
+
+impl Default for BoolTokens {
+    fn default() -> BoolTokens {
+        BoolTokens::new(&["true", "1", "yes"], &["false", "0", "no"])
+    }
+}
+
+impl BoolTokens {
+    pub fn new(true_tokens: &[&str], false_tokens: &[&str]) -> BoolTokens {
+        BoolTokens {
+            true_tokens: true_tokens.iter().map(|x| x.to_lowercase()).collect(),
+            false_tokens: false_tokens.iter().map(|x| x.to_lowercase()).collect(),
+        }
+    }
+
+    pub fn parse(&self, x: &str) -> Option<bool> {
+        let lc_x = x.trim().to_lowercase();
+        if self.true_tokens.contains(&lc_x) {
+            Some(true)
+        } else if self.false_tokens.contains(&lc_x) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    //schema arguments like true=Y|yes;false=N|no, a side that is left out keeps its defaults
+    pub fn from_args(args: &str) -> Result<BoolTokens> {
+        let mut tokens = BoolTokens::default();
+        for item in args.split(';').map(str::trim).filter(|x| !x.is_empty()) {
+            let list = |x: &str| -> Vec<String> {
+                x.split('|').map(|t| t.trim().to_lowercase()).collect()
+            };
+            if let Some(x) = item.strip_prefix("true=") {
+                tokens.true_tokens = list(x);
+            } else if let Some(x) = item.strip_prefix("false=") {
+                tokens.false_tokens = list(x);
+            } else {
+                Err(ColError {
+                    errorcode: ColErrorcode::SchemaSyntax,
+                    error_msg: format!("bool takes true= and false= tokens, got \"{}\"", item),
+                })?;
+            }
+        }
+        if tokens
+            .true_tokens
+            .iter()
+            .any(|x| x.is_empty() || tokens.false_tokens.contains(x))
+            || tokens.false_tokens.iter().any(String::is_empty)
+        {
+            Err(ColError {
+                errorcode: ColErrorcode::SchemaSyntax,
+                error_msg: format!(
+                    "bool tokens must be distinct and non-empty, got \"{}\"",
+                    args
+                ),
+            })?;
+        }
+        Ok(tokens)
+    }
+
+    //the schema arguments of from_args, sides with the default tokens are left out
+    pub fn to_args(&self) -> String {
+        let default = BoolTokens::default();
+        let mut args = Vec::new();
+        if self.true_tokens != default.true_tokens {
+            args.push(format!("true={}", self.true_tokens.join("|")));
+        }
+        if self.false_tokens != default.false_tokens {
+            args.push(format!("false={}", self.false_tokens.join("|")));
+        }
+        args.join(";")
+    }
+}
+
+impl ColBool {
+    pub fn with_tokens(tokens: BoolTokens) -> ColBool {
+        ColBool {
+            data: Bitmap::new(),
+            tokens,
+        }
+    }
+
+    pub fn from_bools(values: &[bool]) -> ColBool {
+        ColBool {
+            data: values.iter().copied().collect(),
+            tokens: BoolTokens::default(),
+        }
+    }
+
+    //row indices where the mask is true
+    pub fn true_indices(&self) -> Vec<usize> {
+        (0..self.data.len()).filter(|&i| self.data.get(i)).collect()
+    }
+}
+
+impl ColBoolNullable {
+    pub fn with_tokens(tokens: BoolTokens) -> ColBoolNullable {
+        ColBoolNullable {
+            data: Bitmap::new(),
+            validity: Bitmap::new(),
+            tokens,
+        }
+    }
+
+    pub fn get(&self, idx: usize) -> Option<bool> {
+        if self.validity.get(idx) {
+            Some(self.data.get(idx))
+        } else {
+            None
+        }
+    }
+
+    //row indices where the mask is true, nulls count as false
+    pub fn true_indices(&self) -> Vec<usize> {
+        (0..self.data.len())
+            .filter(|&i| self.get(i) == Some(true))
+            .collect()
+    }
+}
+
 //implement vanilla i32 and f32 vectors
 #[typetag::serde]
 impl VectorData for ColInt {
@@ -194,6 +1193,9 @@ impl VectorData for ColInt {
     fn reserve(&mut self, additional: usize) {
         self.data.reserve(additional);
     }
+    fn truncate(&mut self, len: usize) {
+        self.data.truncate(len);
+    }
 
     fn boxed_clone(&self) -> Box<dyn VectorData> {
         Box::new(self.clone())
@@ -206,6 +1208,19 @@ impl VectorData for ColInt {
     fn len(&self) -> usize {
         self.data.len()
     }
+    fn get_value(&self, idx: usize) -> Value<'_> {
+        Value::Int(self.data[idx] as i64)
+    }
+    fn take(&self, indices: &[usize]) -> Box<dyn VectorData> {
+        Box::new(ColInt {
+            data: indices.iter().map(|i| self.data[*i]).collect(),
+        })
+    }
+    fn extend(&mut self, other: &dyn VectorData) -> Result<()> {
+        let other: &Self = extend_source(other, self.dtype())?;
+        self.data.extend_from_slice(&other.data);
+        Ok(())
+    }
 }
 
 #[typetag::serde]
@@ -227,6 +1242,9 @@ impl VectorData for ColDouble {
     fn reserve(&mut self, additional: usize) {
         self.data.reserve(additional);
     }
+    fn truncate(&mut self, len: usize) {
+        self.data.truncate(len);
+    }
     fn boxed_clone(&self) -> Box<dyn VectorData> {
         Box::new(self.clone())
     }
@@ -237,32 +1255,46 @@ impl VectorData for ColDouble {
     fn len(&self) -> usize {
         self.data.len()
     }
+    fn get_value(&self, idx: usize) -> Value<'_> {
+        Value::Double(self.data[idx] as f64)
+    }
+    fn take(&self, indices: &[usize]) -> Box<dyn VectorData> {
+        Box::new(ColDouble {
+            data: indices.iter().map(|i| self.data[*i]).collect(),
+        })
+    }
+    fn extend(&mut self, other: &dyn VectorData) -> Result<()> {
+        let other: &Self = extend_source(other, self.dtype())?;
+        self.data.extend_from_slice(&other.data);
+        Ok(())
+    }
 }
 
-//implement nullable
+//implement nullable, null rows hold 0 in data
 #[typetag::serde]
 impl VectorData for ColIntNullable {
     fn push_from_str(&mut self, x: &str) -> Result<()> {
-        self.data.push(x.trim().parse::<i32>().ok());
+        self.push(x.trim().parse::<i32>().ok());
         Ok(())
     }
     fn to_string(&self) -> String {
-        self.data
-            .iter()
-            .map(|x| {
-                if let Some(y) = x {
-                    y.to_string()
-                } else {
-                    String::from("NA")
-                }
+        self.iter()
+            .map(|x| match x {
+                Some(y) => y.to_string(),
+                None => String::from("NA"),
             })
-            .fold(String::new(), |a, b| a + &b[..] + ", ".into())
+            .fold(String::new(), |a, b| a + &b[..] + ", ")
     }
     fn as_any(&self) -> &dyn Any {
         self
     }
     fn reserve(&mut self, additional: usize) {
         self.data.reserve(additional);
+        self.validity.reserve(additional);
+    }
+    fn truncate(&mut self, len: usize) {
+        self.data.truncate(len);
+        self.validity.truncate(len);
     }
     fn boxed_clone(&self) -> Box<dyn VectorData> {
         Box::new(self.clone())
@@ -273,42 +1305,79 @@ impl VectorData for ColIntNullable {
     fn len(&self) -> usize {
         self.data.len()
     }
+    fn get_value(&self, idx: usize) -> Value<'_> {
+        self.get(idx).map_or(Value::Null, |x| Value::Int(x as i64))
+    }
+    fn take(&self, indices: &[usize]) -> Box<dyn VectorData> {
+        Box::new(ColIntNullable {
+            data: indices.iter().map(|i| self.data[*i]).collect(),
+            validity: self.validity.take(indices),
+        })
+    }
+    fn validity(&self) -> Option<&Bitmap> {
+        Some(&self.validity)
+    }
+    fn extend(&mut self, other: &dyn VectorData) -> Result<()> {
+        let other: &Self = extend_source(other, self.dtype())?;
+        self.data.extend_from_slice(&other.data);
+        self.validity.extend(&other.validity);
+        Ok(())
+    }
 }
 
 #[typetag::serde]
 impl VectorData for ColDoubleNullable {
     fn push_from_str(&mut self, x: &str) -> Result<()> {
-        self.data.push(x.trim().parse::<f32>().ok()); //f32 only diff from above
+        self.push(x.trim().parse::<f32>().ok());
         Ok(())
     }
     fn to_string(&self) -> String {
-        self.data[..]
-            .iter()
-            .map(|x| {
-                if let Some(y) = x {
-                    y.to_string()
-                } else {
-                    String::from("NA")
-                }
+        self.iter()
+            .map(|x| match x {
+                Some(y) => y.to_string(),
+                None => String::from("NA"),
             })
-            .fold(String::new(), |a, b| a + &b[..] + ", ".into())
+            .fold(String::new(), |a, b| a + &b[..] + ", ")
     }
     fn as_any(&self) -> &dyn Any {
         self
     }
     fn reserve(&mut self, additional: usize) {
         self.data.reserve(additional);
+        self.validity.reserve(additional);
+    }
+    fn truncate(&mut self, len: usize) {
+        self.data.truncate(len);
+        self.validity.truncate(len);
     }
     fn boxed_clone(&self) -> Box<dyn VectorData> {
         Box::new(self.clone())
     }
     fn dtype(&self) -> Dtype {
-        Dtype::ColDouble
+        Dtype::ColDoubleNullable
     }
-
     fn len(&self) -> usize {
         self.data.len()
     }
+    fn get_value(&self, idx: usize) -> Value<'_> {
+        self.get(idx)
+            .map_or(Value::Null, |x| Value::Double(x as f64))
+    }
+    fn take(&self, indices: &[usize]) -> Box<dyn VectorData> {
+        Box::new(ColDoubleNullable {
+            data: indices.iter().map(|i| self.data[*i]).collect(),
+            validity: self.validity.take(indices),
+        })
+    }
+    fn validity(&self) -> Option<&Bitmap> {
+        Some(&self.validity)
+    }
+    fn extend(&mut self, other: &dyn VectorData) -> Result<()> {
+        let other: &Self = extend_source(other, self.dtype())?;
+        self.data.extend_from_slice(&other.data);
+        self.validity.extend(&other.validity);
+        Ok(())
+    }
 }
 
 #[typetag::serde]
@@ -328,6 +1397,9 @@ impl VectorData for ColString {
     fn reserve(&mut self, additional: usize) {
         self.data.reserve(additional);
     }
+    fn truncate(&mut self, len: usize) {
+        self.data.truncate(len);
+    }
     fn boxed_clone(&self) -> Box<dyn VectorData> {
         Box::new(self.clone())
     }
@@ -339,6 +1411,19 @@ impl VectorData for ColString {
     fn len(&self) -> usize {
         self.data.len()
     }
+    fn get_value(&self, idx: usize) -> Value<'_> {
+        Value::Str(&self.data[idx])
+    }
+    fn take(&self, indices: &[usize]) -> Box<dyn VectorData> {
+        Box::new(ColString {
+            data: indices.iter().map(|i| self.data[*i].clone()).collect(),
+        })
+    }
+    fn extend(&mut self, other: &dyn VectorData) -> Result<()> {
+        let other: &Self = extend_source(other, self.dtype())?;
+        self.data.extend_from_slice(&other.data);
+        Ok(())
+    }
 }
 
 #[typetag::serde]
@@ -356,6 +1441,9 @@ impl VectorData for ColStringPool {
     fn reserve(&mut self, additional: usize) {
         self.data.reserve(additional);
     }
+    fn truncate(&mut self, len: usize) {
+        self.data.truncate(len);
+    }
     fn boxed_clone(&self) -> Box<dyn VectorData> {
         Box::new(self.clone())
     }
@@ -366,4 +1454,388 @@ impl VectorData for ColStringPool {
     fn len(&self) -> usize {
         self.data.len()
     }
+    fn get_value(&self, idx: usize) -> Value<'_> {
+        Value::Str(self.data.get_str(idx))
+    }
+    fn take(&self, indices: &[usize]) -> Box<dyn VectorData> {
+        let mut data = stringpool::StringPool::new();
+        indices
+            .iter()
+            .for_each(|i| data.add_str(self.data.get_str(*i)));
+        Box::new(ColStringPool { data })
+    }
+    fn extend(&mut self, other: &dyn VectorData) -> Result<()> {
+        let other: &Self = extend_source(other, self.dtype())?;
+        self.data.extend(&other.data);
+        Ok(())
+    }
+}
+
+#[typetag::serde]
+impl VectorData for ColStringNullable {
+    fn push_from_str(&mut self, x: &str) -> Result<()> {
+        self.push(Some(x).filter(|x| !x.is_empty()));
+        Ok(())
+    }
+    fn to_string(&self) -> String {
+        (0..self.len())
+            .map(|i| self.get(i).unwrap_or("NA"))
+            .fold(String::new(), |a, b| a + b + ", ")
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+        self.validity.reserve(additional);
+    }
+    fn truncate(&mut self, len: usize) {
+        self.data.truncate(len);
+        self.validity.truncate(len);
+    }
+    fn boxed_clone(&self) -> Box<dyn VectorData> {
+        Box::new(self.clone())
+    }
+    fn dtype(&self) -> Dtype {
+        Dtype::ColStringNullable
+    }
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+    fn get_value(&self, idx: usize) -> Value<'_> {
+        self.get(idx).map_or(Value::Null, Value::Str)
+    }
+    fn take(&self, indices: &[usize]) -> Box<dyn VectorData> {
+        Box::new(ColStringNullable {
+            data: indices.iter().map(|i| self.data[*i].clone()).collect(),
+            validity: self.validity.take(indices),
+        })
+    }
+    fn validity(&self) -> Option<&Bitmap> {
+        Some(&self.validity)
+    }
+    fn extend(&mut self, other: &dyn VectorData) -> Result<()> {
+        let other: &Self = extend_source(other, self.dtype())?;
+        self.data.extend_from_slice(&other.data);
+        self.validity.extend(&other.validity);
+        Ok(())
+    }
+}
+
+#[typetag::serde]
+impl VectorData for ColStringPoolNullable {
+    fn push_from_str(&mut self, x: &str) -> Result<()> {
+        self.push(Some(x).filter(|x| !x.is_empty()));
+        Ok(())
+    }
+    fn to_string(&self) -> String {
+        (0..self.len())
+            .map(|i| self.get(i).unwrap_or("NA"))
+            .fold(String::new(), |a, b| a + b + ", ")
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+        self.validity.reserve(additional);
+    }
+    fn truncate(&mut self, len: usize) {
+        self.data.truncate(len);
+        self.validity.truncate(len);
+    }
+    fn boxed_clone(&self) -> Box<dyn VectorData> {
+        Box::new(self.clone())
+    }
+    fn dtype(&self) -> Dtype {
+        Dtype::ColStringPoolNullable
+    }
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+    fn get_value(&self, idx: usize) -> Value<'_> {
+        self.get(idx).map_or(Value::Null, Value::Str)
+    }
+    fn take(&self, indices: &[usize]) -> Box<dyn VectorData> {
+        let mut data = stringpool::StringPool::new();
+        indices
+            .iter()
+            .for_each(|i| data.add_str(self.data.get_str(*i)));
+        Box::new(ColStringPoolNullable {
+            data,
+            validity: self.validity.take(indices),
+        })
+    }
+    fn validity(&self) -> Option<&Bitmap> {
+        Some(&self.validity)
+    }
+    fn extend(&mut self, other: &dyn VectorData) -> Result<()> {
+        let other: &Self = extend_source(other, self.dtype())?;
+        self.data.extend(&other.data);
+        self.validity.extend(&other.validity);
+        Ok(())
+    }
+}
+
+#[typetag::serde]
+impl VectorData for ColBool {
+    fn push_from_str(&mut self, x: &str) -> Result<()> {
+        let value = self.tokens.parse(x).ok_or_else(|| ColError {
+            errorcode: ColErrorcode::ParseValue,
+            error_msg: format!("cannot parse \"{}\" as bool", x),
+        })?;
+        self.data.push(value);
+        Ok(())
+    }
+    fn to_string(&self) -> String {
+        self.data
+            .iter()
+            .map(|x| x.to_string())
+            .fold(String::new(), |a, b| a + &b[..] + ", ")
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+    }
+    fn truncate(&mut self, len: usize) {
+        self.data.truncate(len);
+    }
+    fn boxed_clone(&self) -> Box<dyn VectorData> {
+        Box::new(self.clone())
+    }
+    fn dtype(&self) -> Dtype {
+        Dtype::ColBool
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+    fn get_value(&self, idx: usize) -> Value<'_> {
+        Value::Bool(self.data.get(idx))
+    }
+    fn take(&self, indices: &[usize]) -> Box<dyn VectorData> {
+        Box::new(ColBool {
+            data: indices.iter().map(|i| self.data.get(*i)).collect(),
+            tokens: self.tokens.clone(),
+        })
+    }
+    fn extend(&mut self, other: &dyn VectorData) -> Result<()> {
+        let other: &Self = extend_source(other, self.dtype())?;
+        self.data.extend(&other.data);
+        Ok(())
+    }
+    fn type_args(&self) -> String {
+        self.tokens.to_args()
+    }
+}
+
+#[typetag::serde]
+impl VectorData for ColBoolNullable {
+    fn push_from_str(&mut self, x: &str) -> Result<()> {
+        let value = self.tokens.parse(x);
+        self.data.push(value.unwrap_or(false));
+        self.validity.push(value.is_some());
+        Ok(())
+    }
+    fn to_string(&self) -> String {
+        (0..self.len())
+            .map(|i| match self.get(i) {
+                Some(y) => y.to_string(),
+                None => String::from("NA"),
+            })
+            .fold(String::new(), |a, b| a + &b[..] + ", ")
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+        self.validity.reserve(additional);
+    }
+    fn truncate(&mut self, len: usize) {
+        self.data.truncate(len);
+        self.validity.truncate(len);
+    }
+    fn boxed_clone(&self) -> Box<dyn VectorData> {
+        Box::new(self.clone())
+    }
+    fn dtype(&self) -> Dtype {
+        Dtype::ColBoolNullable
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+    fn get_value(&self, idx: usize) -> Value<'_> {
+        self.get(idx).map_or(Value::Null, Value::Bool)
+    }
+    fn take(&self, indices: &[usize]) -> Box<dyn VectorData> {
+        Box::new(ColBoolNullable {
+            data: indices.iter().map(|i| self.data.get(*i)).collect(),
+            validity: self.validity.take(indices),
+            tokens: self.tokens.clone(),
+        })
+    }
+    fn validity(&self) -> Option<&Bitmap> {
+        Some(&self.validity)
+    }
+    fn extend(&mut self, other: &dyn VectorData) -> Result<()> {
+        let other: &Self = extend_source(other, self.dtype())?;
+        self.data.extend(&other.data);
+        self.validity.extend(&other.validity);
+        Ok(())
+    }
+    fn type_args(&self) -> String {
+        self.tokens.to_args()
+    }
 }
+
+//numeric columns of wider types share one implementation, $value wraps an element as Value
+macro_rules! numeric_columns {
+    ($col:ident, $col_nullable:ident, $t:ty, $dtype:ident, $dtype_nullable:ident, $value:expr) => {
+        #[derive(Clone, Debug, PartialEq, PartialOrd, Default, Serialize, Deserialize)]
+        pub struct $col {
+            pub data: Vec<$t>,
+        }
+
+        #[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+        pub struct $col_nullable {
+            pub data: Vec<$t>,
+            pub validity: Bitmap,
+        }
+
+        nullable_values!($col_nullable, $t);
+
+        #[typetag::serde]
+        impl VectorData for $col {
+            fn push_from_str(&mut self, x: &str) -> Result<()> {
+                let value = x.trim().parse::<$t>()?;
+                self.data.push(value);
+                Ok(())
+            }
+            fn to_string(&self) -> String {
+                self.data
+                    .iter()
+                    .map(|x| x.to_string())
+                    .fold(String::new(), |a, b| a + &b[..] + ", ")
+            }
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+            fn reserve(&mut self, additional: usize) {
+                self.data.reserve(additional);
+            }
+            fn truncate(&mut self, len: usize) {
+                self.data.truncate(len);
+            }
+            fn boxed_clone(&self) -> Box<dyn VectorData> {
+                Box::new(self.clone())
+            }
+            fn dtype(&self) -> Dtype {
+                Dtype::$dtype
+            }
+
+            fn len(&self) -> usize {
+                self.data.len()
+            }
+            fn get_value(&self, idx: usize) -> Value<'_> {
+                ($value)(self.data[idx])
+            }
+            fn take(&self, indices: &[usize]) -> Box<dyn VectorData> {
+                Box::new($col {
+                    data: indices.iter().map(|i| self.data[*i]).collect(),
+                })
+            }
+            fn extend(&mut self, other: &dyn VectorData) -> Result<()> {
+                let other: &Self = extend_source(other, self.dtype())?;
+                self.data.extend_from_slice(&other.data);
+                Ok(())
+            }
+        }
+
+        #[typetag::serde]
+        impl VectorData for $col_nullable {
+            fn push_from_str(&mut self, x: &str) -> Result<()> {
+                self.push(x.trim().parse::<$t>().ok());
+                Ok(())
+            }
+            fn to_string(&self) -> String {
+                self.iter()
+                    .map(|x| match x {
+                        Some(y) => y.to_string(),
+                        None => String::from("NA"),
+                    })
+                    .fold(String::new(), |a, b| a + &b[..] + ", ")
+            }
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+            fn reserve(&mut self, additional: usize) {
+                self.data.reserve(additional);
+                self.validity.reserve(additional);
+            }
+            fn truncate(&mut self, len: usize) {
+                self.data.truncate(len);
+                self.validity.truncate(len);
+            }
+            fn boxed_clone(&self) -> Box<dyn VectorData> {
+                Box::new(self.clone())
+            }
+            fn dtype(&self) -> Dtype {
+                Dtype::$dtype_nullable
+            }
+
+            fn len(&self) -> usize {
+                self.data.len()
+            }
+            fn get_value(&self, idx: usize) -> Value<'_> {
+                self.get(idx).map_or(Value::Null, $value)
+            }
+            fn take(&self, indices: &[usize]) -> Box<dyn VectorData> {
+                Box::new($col_nullable {
+                    data: indices.iter().map(|i| self.data[*i]).collect(),
+                    validity: self.validity.take(indices),
+                })
+            }
+            fn validity(&self) -> Option<&Bitmap> {
+                Some(&self.validity)
+            }
+            fn extend(&mut self, other: &dyn VectorData) -> Result<()> {
+                let other: &Self = extend_source(other, self.dtype())?;
+                self.data.extend_from_slice(&other.data);
+                self.validity.extend(&other.validity);
+                Ok(())
+            }
+        }
+    };
+}
+
+numeric_columns!(
+    ColI64,
+    ColI64Nullable,
+    i64,
+    ColI64,
+    ColI64Nullable,
+    Value::Int
+);
+numeric_columns!(ColU32, ColU32Nullable, u32, ColU32, ColU32Nullable, |x| {
+    Value::Int(x as i64)
+});
+numeric_columns!(
+    ColU64,
+    ColU64Nullable,
+    u64,
+    ColU64,
+    ColU64Nullable,
+    Value::UInt
+);
+numeric_columns!(
+    ColF64,
+    ColF64Nullable,
+    f64,
+    ColF64,
+    ColF64Nullable,
+    Value::Double
+);
@@ -0,0 +1,284 @@
+use super::column::{extend_source, ColError, ColErrorcode, Dtype, Value, VectorData};
+use crate::bitmap::Bitmap;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::error;
+
+type Result<T> = std::result::Result<T, Box<dyn error::Error>>;
+
+//how binary cells are read from and written to text
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BinaryEncoding {
+    #[default]
+    Hex,
+    Base64,
+}
+
+fn binary_err(error_msg: String) -> Box<dyn error::Error> {
+    Box::new(ColError {
+        errorcode: ColErrorcode::ParseValue,
+        error_msg,
+    })
+}
+
+impl BinaryEncoding {
+    pub fn from_str_to_res(s: &str) -> Result<BinaryEncoding> {
+        match &s.trim().to_lowercase()[..] {
+            "hex" => Ok(BinaryEncoding::Hex),
+            "base64" => Ok(BinaryEncoding::Base64),
+            _ => Err(Box::new(ColError {
+                errorcode: ColErrorcode::SchemaSyntax,
+                error_msg: format!("binary encoding is hex or base64, got \"{}\"", s),
+            })),
+        }
+    }
+
+    pub fn encode(&self, bytes: &[u8]) -> String {
+        match self {
+            BinaryEncoding::Hex => hex::encode(bytes),
+            BinaryEncoding::Base64 => BASE64.encode(bytes),
+        }
+    }
+
+    //hex may carry a 0x prefix and upper case digits
+    pub fn decode(&self, x: &str) -> Result<Vec<u8>> {
+        let x = x.trim();
+        let decoded = match self {
+            BinaryEncoding::Hex => {
+                let digits = x
+                    .strip_prefix("0x")
+                    .or_else(|| x.strip_prefix("0X"))
+                    .unwrap_or(x);
+                hex::decode(digits).map_err(|e| e.to_string())
+            }
+            BinaryEncoding::Base64 => BASE64.decode(x).map_err(|e| e.to_string()),
+        };
+        decoded.map_err(|e| binary_err(format!("cannot parse \"{}\" as {:?}: {}", x, self, e)))
+    }
+}
+
+//byte strings stored back to back in bytes, row i is bytes[offsets[i]..offsets[i + 1]]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColBinary {
+    pub offsets: Vec<usize>,
+    pub bytes: Vec<u8>,
+    pub encoding: BinaryEncoding,
+}
+
+//null rows are empty in bytes
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColBinaryNullable {
+    pub offsets: Vec<usize>,
+    pub bytes: Vec<u8>,
+    pub validity: Bitmap,
+    pub encoding: BinaryEncoding,
+}
+
+impl Default for ColBinary {
+    fn default() -> ColBinary {
+        ColBinary::new(BinaryEncoding::default())
+    }
+}
+
+impl Default for ColBinaryNullable {
+    fn default() -> ColBinaryNullable {
+        ColBinaryNullable::new(BinaryEncoding::default())
+    }
+}
+
+impl ColBinary {
+    pub fn new(encoding: BinaryEncoding) -> ColBinary {
+        ColBinary {
+            offsets: vec![0],
+            bytes: Vec::new(),
+            encoding,
+        }
+    }
+
+    pub fn push(&mut self, value: &[u8]) {
+        self.bytes.extend_from_slice(value);
+        self.offsets.push(self.bytes.len());
+    }
+
+    pub fn get(&self, idx: usize) -> &[u8] {
+        &self.bytes[self.offsets[idx]..self.offsets[idx + 1]]
+    }
+}
+
+impl ColBinaryNullable {
+    pub fn new(encoding: BinaryEncoding) -> ColBinaryNullable {
+        ColBinaryNullable {
+            offsets: vec![0],
+            bytes: Vec::new(),
+            validity: Bitmap::new(),
+            encoding,
+        }
+    }
+
+    pub fn push(&mut self, value: Option<&[u8]>) {
+        self.bytes.extend_from_slice(value.unwrap_or_default());
+        self.offsets.push(self.bytes.len());
+        self.validity.push(value.is_some());
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&[u8]> {
+        if self.validity.get(idx) {
+            Some(&self.bytes[self.offsets[idx]..self.offsets[idx + 1]])
+        } else {
+            None
+        }
+    }
+}
+
+#[typetag::serde]
+impl VectorData for ColBinary {
+    fn push_from_str(&mut self, x: &str) -> Result<()> {
+        let value = self.encoding.decode(x)?;
+        self.push(&value);
+        Ok(())
+    }
+    fn to_string(&self) -> String {
+        (0..self.len())
+            .map(|i| self.encoding.encode(self.get(i)))
+            .fold(String::new(), |a, b| a + &b[..] + ", ")
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn reserve(&mut self, additional: usize) {
+        self.offsets.reserve(additional);
+    }
+    fn truncate(&mut self, len: usize) {
+        self.offsets.truncate(len + 1);
+        self.bytes.truncate(*self.offsets.last().unwrap());
+    }
+    fn boxed_clone(&self) -> Box<dyn VectorData> {
+        Box::new(self.clone())
+    }
+    fn dtype(&self) -> Dtype {
+        Dtype::ColBinary
+    }
+    fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+    fn get_value(&self, idx: usize) -> Value<'_> {
+        Value::Binary(self.get(idx))
+    }
+    fn take(&self, indices: &[usize]) -> Box<dyn VectorData> {
+        let mut out = ColBinary::new(self.encoding);
+        indices.iter().for_each(|i| out.push(self.get(*i)));
+        Box::new(out)
+    }
+    fn type_args(&self) -> String {
+        match self.encoding {
+            BinaryEncoding::Hex => String::new(),
+            BinaryEncoding::Base64 => String::from("base64"),
+        }
+    }
+    fn extend(&mut self, other: &dyn VectorData) -> Result<()> {
+        let other: &Self = extend_source(other, self.dtype())?;
+        let base = self.bytes.len();
+        self.bytes.extend_from_slice(&other.bytes);
+        self.offsets
+            .extend(other.offsets[1..].iter().map(|o| base + o));
+        Ok(())
+    }
+}
+
+#[typetag::serde]
+impl VectorData for ColBinaryNullable {
+    //empty cells are null, malformed encodings are still an error
+    fn push_from_str(&mut self, x: &str) -> Result<()> {
+        if x.trim().is_empty() {
+            self.push(None);
+        } else {
+            let value = self.encoding.decode(x)?;
+            self.push(Some(&value));
+        }
+        Ok(())
+    }
+    fn to_string(&self) -> String {
+        (0..self.len())
+            .map(|i| match self.get(i) {
+                Some(y) => self.encoding.encode(y),
+                None => String::from("NA"),
+            })
+            .fold(String::new(), |a, b| a + &b[..] + ", ")
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn reserve(&mut self, additional: usize) {
+        self.offsets.reserve(additional);
+        self.validity.reserve(additional);
+    }
+    fn truncate(&mut self, len: usize) {
+        self.offsets.truncate(len + 1);
+        self.bytes.truncate(*self.offsets.last().unwrap());
+        self.validity.truncate(len);
+    }
+    fn boxed_clone(&self) -> Box<dyn VectorData> {
+        Box::new(self.clone())
+    }
+    fn dtype(&self) -> Dtype {
+        Dtype::ColBinaryNullable
+    }
+    fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+    fn get_value(&self, idx: usize) -> Value<'_> {
+        self.get(idx).map_or(Value::Null, Value::Binary)
+    }
+    fn take(&self, indices: &[usize]) -> Box<dyn VectorData> {
+        let mut out = ColBinaryNullable::new(self.encoding);
+        indices.iter().for_each(|i| out.push(self.get(*i)));
+        Box::new(out)
+    }
+    fn validity(&self) -> Option<&Bitmap> {
+        Some(&self.validity)
+    }
+    fn type_args(&self) -> String {
+        match self.encoding {
+            BinaryEncoding::Hex => String::new(),
+            BinaryEncoding::Base64 => String::from("base64"),
+        }
+    }
+    fn extend(&mut self, other: &dyn VectorData) -> Result<()> {
+        let other: &Self = extend_source(other, self.dtype())?;
+        let base = self.bytes.len();
+        self.bytes.extend_from_slice(&other.bytes);
+        self.offsets
+            .extend(other.offsets[1..].iter().map(|o| base + o));
+        self.validity.extend(&other.validity);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_and_base64() {
+        let mut col = ColBinary::default();
+        col.push_from_str("0xDEADbeef").unwrap();
+        col.push_from_str("").unwrap();
+        assert_eq!(col.get(0), &[0xde, 0xad, 0xbe, 0xef]);
+        assert!(col.get(1).is_empty());
+        assert_eq!(VectorData::to_string(&col), "deadbeef, , ");
+        assert!(col.push_from_str("abc").is_err());
+
+        let mut col = ColBinaryNullable::new(BinaryEncoding::Base64);
+        col.push_from_str("aGVsbG8=").unwrap();
+        col.push_from_str("").unwrap();
+        assert_eq!(col.get(0), Some(&b"hello"[..]));
+        assert_eq!(col.null_count(), 1);
+        assert_eq!(VectorData::to_string(&col), "aGVsbG8=, NA, ");
+
+        let taken = col.take(&[1, 0]);
+        assert_eq!(taken.get_value(1), Value::Binary(b"hello"));
+        assert_eq!(taken.get_value(0), Value::Null);
+    }
+}
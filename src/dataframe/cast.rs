@@ -0,0 +1,675 @@
+use super::column::{
+    BinaryEncoding, BoolTokens, ColBinary, ColBinaryNullable, ColBool, ColBoolNullable, ColDouble,
+    ColDoubleNullable, ColError, ColErrorcode, ColF64, ColF64Nullable, ColI64, ColI64Nullable,
+    ColInt, ColIntNullable, ColString, ColStringNullable, ColStringPool, ColStringPoolNullable,
+    ColU32, ColU32Nullable, ColU64, ColU64Nullable, Column, Decimal, Dtype, RoundingMode, TimeUnit,
+    Value, VectorData,
+};
+use super::{nested, temporal};
+use crate::bitmap::Bitmap;
+use std::error;
+
+type Result<T> = std::result::Result<T, Box<dyn error::Error>>;
+
+//strict casts fail on the first cell that does not convert, lenient casts make it null
+//and then give the nullable variant of the target dtype
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CastOptions {
+    pub strict: bool,
+    pub rounding: RoundingMode, //for doubles cast to decimals
+}
+
+impl Default for CastOptions {
+    fn default() -> CastOptions {
+        CastOptions::strict()
+    }
+}
+
+impl CastOptions {
+    pub fn strict() -> CastOptions {
+        CastOptions {
+            strict: true,
+            rounding: RoundingMode::default(),
+        }
+    }
+
+    pub fn lenient() -> CastOptions {
+        CastOptions {
+            strict: false,
+            ..CastOptions::strict()
+        }
+    }
+}
+
+fn cast_err(error_msg: String) -> Box<dyn error::Error> {
+    Box::new(ColError {
+        errorcode: ColErrorcode::Cast,
+        error_msg,
+    })
+}
+
+fn unsupported(value: Value, target: Dtype) -> Box<dyn error::Error> {
+    cast_err(format!("no cast of \"{}\" to {}", value, target))
+}
+
+fn ticks_per_day(unit: TimeUnit) -> i64 {
+    unit.ticks_per_second() * 86_400
+}
+
+//ticks in another unit, going to a coarser unit rounds towards negative infinity
+fn rescale_ticks(ticks: i64, from: TimeUnit, to: TimeUnit) -> Result<i64> {
+    let (f, t) = (from.ticks_per_second(), to.ticks_per_second());
+    if t >= f {
+        ticks
+            .checked_mul(t / f)
+            .ok_or_else(|| cast_err(format!("{} {} overflows in {}", ticks, from, to)))
+    } else {
+        Ok(ticks.div_euclid(f / t))
+    }
+}
+
+//days and ticks outside the calendar range do not cast
+fn date_text(days: i64) -> Result<String> {
+    i32::try_from(days)
+        .ok()
+        .and_then(|x| temporal::format_date(x, None).ok())
+        .ok_or_else(|| cast_err(format!("{} days are out of the date range", days)))
+}
+
+fn datetime_text(ticks: i64, unit: TimeUnit) -> Result<String> {
+    temporal::format_datetime(ticks, None, unit, None)
+        .map_err(|_| cast_err(format!("{} {} is out of the datetime range", ticks, unit)))
+}
+
+//element types of the numeric columns, which cast into each other without text. ints take
+//whole numbers in their range, f32 takes finite values in its range
+pub(crate) trait Number: Copy + Default {
+    fn from_int(x: i128) -> Option<Self>;
+    fn from_double(x: f64) -> Option<Self>;
+}
+
+macro_rules! int_number {
+    ($($t:ty),*) => {$(
+        impl Number for $t {
+            fn from_int(x: i128) -> Option<$t> {
+                <$t>::try_from(x).ok()
+            }
+            //doubles beyond i128 saturate and then fail try_from
+            fn from_double(x: f64) -> Option<$t> {
+                if !x.is_finite() || x.fract() != 0.0 {
+                    return None;
+                }
+                <$t>::try_from(x as i128).ok()
+            }
+        }
+    )*};
+}
+int_number!(i32, i64, u32, u64);
+
+impl Number for f32 {
+    fn from_int(x: i128) -> Option<f32> {
+        Some(x as f32)
+    }
+    fn from_double(x: f64) -> Option<f32> {
+        if x.is_finite() && x.abs() > f32::MAX as f64 {
+            return None;
+        }
+        Some(x as f32)
+    }
+}
+
+impl Number for f64 {
+    fn from_int(x: i128) -> Option<f64> {
+        Some(x as f64)
+    }
+    fn from_double(x: f64) -> Option<f64> {
+        Some(x)
+    }
+}
+
+fn is_number(dtype: Dtype) -> bool {
+    matches!(
+        dtype.non_nullable(),
+        Dtype::ColInt
+            | Dtype::ColI64
+            | Dtype::ColU32
+            | Dtype::ColU64
+            | Dtype::ColDouble
+            | Dtype::ColF64
+    )
+}
+
+pub(crate) fn to_number<T: Number>(value: Value, target: Dtype) -> Result<Option<T>> {
+    let x = match value {
+        Value::Null => return Ok(None),
+        Value::Int(x) => T::from_int(x as i128),
+        Value::UInt(x) => T::from_int(x as i128),
+        Value::Double(x) => T::from_double(x),
+        _ => return Err(unsupported(value, target)),
+    };
+    x.map(Some)
+        .ok_or_else(|| cast_err(format!("{} does not fit {}", value, target)))
+}
+
+//numeric column of the target dtype from the cells of col
+macro_rules! numeric_column {
+    ($col:expr, $dtype:expr, $target:expr, $options:expr, $out:ident, $out_nullable:ident) => {{
+        let (data, validity) = $col.numbers($dtype, $target, $options)?;
+        let out: Box<dyn VectorData> = if $target.is_nullable() {
+            Box::new($out_nullable { data, validity })
+        } else {
+            Box::new($out { data })
+        };
+        out
+    }};
+}
+
+//numbers as text, the parsing of the target column checks overflow and fractions
+fn numeric_text(value: Value, target: Dtype, options: &CastOptions) -> Result<String> {
+    let text = match value {
+        Value::Int(x) => x.to_string(),
+        Value::UInt(x) => x.to_string(),
+        Value::Bool(x) => (x as u8).to_string(),
+        Value::Double(x) => match target {
+            Dtype::ColDecimal(_, scale) => {
+                Decimal::from_f64(x, scale, options.rounding)?.to_string()
+            }
+            _ => x.to_string(),
+        },
+        Value::Decimal(d) => match target {
+            Dtype::ColDouble | Dtype::ColF64 => d.to_f64().to_string(),
+            Dtype::ColDecimal(..) => d.to_string(),
+            _ => {
+                let whole = d.rescale(0, RoundingMode::Down)?;
+                if whole.rescale(d.scale, RoundingMode::Down)? != d {
+                    return Err(cast_err(format!("{} is not a whole number", d)));
+                }
+                whole.to_string()
+            }
+        },
+        Value::Date(x) => x.to_string(),
+        Value::Datetime(x, _) | Value::Duration(x, _) => x.to_string(),
+        Value::Str(x) => x.to_string(),
+        _ => return Err(unsupported(value, target)),
+    };
+    Ok(text)
+}
+
+//the bool, str or bytes of a value, None for null
+fn as_bool(value: Value) -> Option<bool> {
+    match value {
+        Value::Bool(x) => Some(x),
+        _ => None,
+    }
+}
+
+fn as_str(value: Value<'_>) -> Option<&str> {
+    match value {
+        Value::Str(x) => Some(x),
+        _ => None,
+    }
+}
+
+fn as_bytes(value: Value<'_>) -> Option<&[u8]> {
+    match value {
+        Value::Binary(x) => Some(x),
+        _ => None,
+    }
+}
+
+//text the target column parses back to the cast value, None for null
+fn cast_text(value: Value, target: Dtype, options: &CastOptions) -> Result<Option<String>> {
+    let base = target.non_nullable();
+    let text = match (base, value) {
+        (_, Value::Null) => return Ok(None),
+        (Dtype::ColString | Dtype::ColStringPool | Dtype::ColCategorical, Value::Binary(x)) => {
+            String::from_utf8(x.to_vec()).map_err(|e| cast_err(e.to_string()))?
+        }
+        (Dtype::ColString | Dtype::ColStringPool | Dtype::ColCategorical, v) => v.to_string(),
+        //strings become their utf8 bytes
+        (Dtype::ColBinary, Value::Str(x)) => BinaryEncoding::Hex.encode(x.as_bytes()),
+        (Dtype::ColBinary, Value::Binary(x)) => BinaryEncoding::Hex.encode(x),
+        (Dtype::ColList | Dtype::ColStruct, v @ (Value::List(..) | Value::Struct(..))) => {
+            nested::value_to_json(v).to_string()
+        }
+        (Dtype::ColList | Dtype::ColStruct, Value::Str(x)) => x.to_string(),
+        (
+            Dtype::ColInt
+            | Dtype::ColI64
+            | Dtype::ColU32
+            | Dtype::ColU64
+            | Dtype::ColDouble
+            | Dtype::ColF64
+            | Dtype::ColDecimal(..),
+            v,
+        ) => numeric_text(v, base, options)?,
+        (Dtype::ColBool, v) => match v {
+            Value::Bool(x) => x.to_string(),
+            Value::Int(0) | Value::UInt(0) => String::from("false"),
+            Value::Int(1) | Value::UInt(1) => String::from("true"),
+            Value::Double(x) if x == 0.0 || x == 1.0 => (x == 1.0).to_string(),
+            Value::Str(x) => x.to_string(),
+            _ => return Err(unsupported(v, target)),
+        },
+        (Dtype::ColDate, v) => match v {
+            Value::Date(x) => date_text(x as i64)?,
+            Value::Datetime(x, unit) => date_text(x.div_euclid(ticks_per_day(unit)))?,
+            Value::Int(x) => date_text(x)?,
+            Value::Str(x) => x.to_string(),
+            _ => return Err(unsupported(v, target)),
+        },
+        (Dtype::ColDatetime(unit), v) => {
+            let ticks = match v {
+                Value::Datetime(x, from) => rescale_ticks(x, from, unit)?,
+                Value::Date(x) => (x as i64)
+                    .checked_mul(ticks_per_day(unit))
+                    .ok_or_else(|| cast_err(format!("date {} overflows in {}", x, unit)))?,
+                Value::Int(x) => x,
+                Value::Str(x) => return Ok(Some(x.to_string())),
+                _ => return Err(unsupported(v, target)),
+            };
+            datetime_text(ticks, unit)?
+        }
+        //durations parse integers as ticks
+        (Dtype::ColDuration(unit), v) => match v {
+            Value::Duration(x, from) => rescale_ticks(x, from, unit)?.to_string(),
+            Value::Int(x) => x.to_string(),
+            Value::Str(x) => x.to_string(),
+            _ => return Err(unsupported(v, target)),
+        },
+        (_, v) => return Err(unsupported(v, target)),
+    };
+    Ok(Some(text))
+}
+
+impl Column {
+    //the column converted to dtype, e.g. strings parsed to ints or ints to doubleNullable
+    pub fn cast(&self, dtype: Dtype, options: CastOptions) -> Result<Column> {
+        let target = if options.strict {
+            dtype
+        } else {
+            dtype.nullable()
+        };
+        if target == self.dtype() {
+            return Ok(self.clone());
+        }
+        if is_number(self.dtype()) && is_number(target) {
+            let data = match target.non_nullable() {
+                Dtype::ColInt => {
+                    numeric_column!(self, dtype, target, options, ColInt, ColIntNullable)
+                }
+                Dtype::ColI64 => {
+                    numeric_column!(self, dtype, target, options, ColI64, ColI64Nullable)
+                }
+                Dtype::ColU32 => {
+                    numeric_column!(self, dtype, target, options, ColU32, ColU32Nullable)
+                }
+                Dtype::ColU64 => {
+                    numeric_column!(self, dtype, target, options, ColU64, ColU64Nullable)
+                }
+                Dtype::ColF64 => {
+                    numeric_column!(self, dtype, target, options, ColF64, ColF64Nullable)
+                }
+                _ => numeric_column!(self, dtype, target, options, ColDouble, ColDoubleNullable),
+            };
+            return Ok(self.with_data(data));
+        }
+        if let Some(data) = self.copied(dtype, target)? {
+            return Ok(self.with_data(data));
+        }
+
+        //a cast to the nullable variant keeps type arguments like list elements and formats
+        let mut out = if target.non_nullable() == self.dtype().non_nullable() {
+            Column::new_with_args(self.name.clone(), target, &self.data.type_args())?
+        } else {
+            Column::new(self.name.clone(), target)
+        };
+        out.data.reserve(self.data.len());
+        for i in 0..self.data.len() {
+            let value = self.data.get_value(i);
+            let pushed = cast_text(value, target, &options).and_then(|text| match text {
+                //nullable columns push unparsable text as null, which is still a failed cast
+                Some(x) => {
+                    out.data.push_from_str(&x)?;
+                    if !x.is_empty() && out.data.is_null(i) {
+                        return Err(cast_err(format!("\"{}\" does not parse", x)));
+                    }
+                    Ok(())
+                }
+                None if target.is_nullable() => out.data.push_from_str(""),
+                None => Err(cast_err(String::from("null in a non-nullable dtype"))),
+            });
+            if let Err(e) = pushed {
+                if options.strict {
+                    return Err(self.row_err(i, value, dtype, e));
+                }
+                if out.data.len() == i {
+                    out.data.push_from_str("")?;
+                }
+            }
+        }
+        Ok(self.with_data(out.data))
+    }
+
+    //bools, strings and binaries are copied instead of parsed back from text, which would
+    //lose bool tokens and make empty strings null. None for other dtypes
+    fn copied(&self, dtype: Dtype, target: Dtype) -> Result<Option<Box<dyn VectorData>>> {
+        let from = self.dtype().non_nullable();
+        let copies = match target.non_nullable() {
+            Dtype::ColBool | Dtype::ColBinary => from == target.non_nullable(),
+            Dtype::ColString | Dtype::ColStringPool => matches!(
+                from,
+                Dtype::ColString | Dtype::ColStringPool | Dtype::ColCategorical
+            ),
+            _ => false,
+        };
+        if !copies {
+            return Ok(None);
+        }
+        //lenient casts have a nullable target, only strict ones fail on nulls
+        if !target.is_nullable() {
+            if let Some(i) = (0..self.data.len()).find(|i| self.data.is_null(*i)) {
+                let e = cast_err(String::from("null in a non-nullable dtype"));
+                return Err(self.row_err(i, Value::Null, dtype, e));
+            }
+        }
+
+        let values = (0..self.data.len()).map(|i| self.data.get_value(i));
+        let encoding = BinaryEncoding::from_str_to_res(&self.data.type_args()).unwrap_or_default();
+        let data: Box<dyn VectorData> = match target {
+            Dtype::ColBool => {
+                let mut out = ColBool::with_tokens(self.bool_tokens()?);
+                values.for_each(|v| out.data.push(as_bool(v).unwrap_or_default()));
+                Box::new(out)
+            }
+            Dtype::ColBoolNullable => {
+                let mut out = ColBoolNullable::with_tokens(self.bool_tokens()?);
+                for x in values.map(as_bool) {
+                    out.data.push(x.unwrap_or_default());
+                    out.validity.push(x.is_some());
+                }
+                Box::new(out)
+            }
+            Dtype::ColString => {
+                let mut out = ColString::default();
+                values.for_each(|v| out.data.push(as_str(v).unwrap_or("").to_string()));
+                Box::new(out)
+            }
+            Dtype::ColStringNullable => {
+                let mut out = ColStringNullable::default();
+                values.for_each(|v| out.push(as_str(v)));
+                Box::new(out)
+            }
+            Dtype::ColStringPool => {
+                let mut out = ColStringPool::default();
+                values.for_each(|v| out.data.add_str(as_str(v).unwrap_or("")));
+                Box::new(out)
+            }
+            Dtype::ColStringPoolNullable => {
+                let mut out = ColStringPoolNullable::default();
+                values.for_each(|v| out.push(as_str(v)));
+                Box::new(out)
+            }
+            Dtype::ColBinary => {
+                let mut out = ColBinary::new(encoding);
+                values.for_each(|v| out.push(as_bytes(v).unwrap_or_default()));
+                Box::new(out)
+            }
+            _ => {
+                let mut out = ColBinaryNullable::new(encoding);
+                values.for_each(|v| out.push(as_bytes(v)));
+                Box::new(out)
+            }
+        };
+        Ok(Some(data))
+    }
+
+    fn bool_tokens(&self) -> Result<BoolTokens> {
+        match self.get::<ColBool>() {
+            Ok(col) => Ok(col.tokens.clone()),
+            Err(_) => Ok(self.get::<ColBoolNullable>()?.tokens.clone()),
+        }
+    }
+
+    //the values and validity of a cast between numeric columns
+    fn numbers<T: Number>(
+        &self,
+        dtype: Dtype,
+        target: Dtype,
+        options: CastOptions,
+    ) -> Result<(Vec<T>, Bitmap)> {
+        let n = self.data.len();
+        let (mut data, mut validity) = (Vec::with_capacity(n), Bitmap::with_capacity(n));
+        for i in 0..n {
+            let value = self.data.get_value(i);
+            let x = match to_number::<T>(value, target) {
+                Ok(None) if !target.is_nullable() => {
+                    Err(cast_err(String::from("null in a non-nullable dtype")))
+                }
+                x => x,
+            };
+            let x = match x {
+                Ok(x) => x,
+                Err(e) if options.strict => return Err(self.row_err(i, value, dtype, e)),
+                Err(_) => None,
+            };
+            data.push(x.unwrap_or_default());
+            validity.push(x.is_some());
+        }
+        Ok((data, validity))
+    }
+
+    fn row_err(
+        &self,
+        i: usize,
+        value: Value,
+        dtype: Dtype,
+        e: Box<dyn error::Error>,
+    ) -> Box<dyn error::Error> {
+        cast_err(format!(
+            "row {} of column \"{}\", cannot cast \"{}\" to {}: {}",
+            i,
+            self.name.as_deref().unwrap_or("None"),
+            value,
+            dtype,
+            e
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataframe::csv_read_str_iter;
+
+    #[test]
+    fn cast_strings_and_numbers() {
+        let df = csv_read_str_iter("1,3000000000,2.5\n-2,7,x", "a:string,b:i64,c:string").unwrap();
+        let a = df.data[0]
+            .cast(Dtype::ColInt, CastOptions::strict())
+            .unwrap();
+        assert_eq!(a.as_i32().unwrap(), &[1, -2]);
+
+        let a = a
+            .cast(Dtype::ColDoubleNullable, CastOptions::strict())
+            .unwrap();
+        let a_opt: Vec<_> = a.as_f32_opt().unwrap().collect();
+        assert_eq!(a_opt, vec![Some(1.0), Some(-2.0)]);
+        let a = a.cast(Dtype::ColStringPool, CastOptions::strict()).unwrap();
+        assert_eq!(a.data.to_string(), "[ \"1\", \"-2\",]");
+
+        //narrowing checks overflow
+        let err = df.data[1].cast(Dtype::ColInt, CastOptions::strict());
+        assert!(err
+            .unwrap_err()
+            .to_string()
+            .contains("row 0 of column \"b\""));
+        let b = df.data[1]
+            .cast(Dtype::ColInt, CastOptions::lenient())
+            .unwrap();
+        assert_eq!(b.dtype(), Dtype::ColIntNullable);
+        assert_eq!(b.data.to_string(), "NA, 7, ");
+
+        assert!(df.data[2]
+            .cast(Dtype::ColF64, CastOptions::strict())
+            .is_err());
+        let c = df.data[2]
+            .cast(Dtype::ColF64, CastOptions::lenient())
+            .unwrap();
+        assert_eq!(c.data.to_string(), "2.5, NA, ");
+        assert!(
+            c.cast(Dtype::ColF64, CastOptions::strict()).is_err(),
+            "null"
+        );
+        assert!(
+            c.cast(Dtype::ColI64Nullable, CastOptions::strict())
+                .is_err(),
+            "2.5"
+        );
+    }
+
+    #[test]
+    fn cast_numbers_directly() {
+        let myschema = "a:u64,b:f64Nullable,c:i64";
+        let df = csv_read_str_iter("18446744073709551615,2.5,-1\n7,,9007199254740993", myschema)
+            .unwrap();
+
+        assert!(df.data[0]
+            .cast(Dtype::ColI64, CastOptions::strict())
+            .is_err());
+        let a = df.data[0]
+            .cast(Dtype::ColInt, CastOptions::lenient())
+            .unwrap();
+        assert_eq!(a.data.to_string(), "NA, 7, ");
+        let a = df.data[0]
+            .cast(Dtype::ColF64, CastOptions::strict())
+            .unwrap();
+        assert_eq!(a.as_f64().unwrap(), &[1.8446744073709552e19, 7.0]);
+
+        //fractions and nulls fail strict casts to non-nullable ints
+        assert!(df.data[1]
+            .cast(Dtype::ColI64Nullable, CastOptions::strict())
+            .is_err());
+        let b = df.data[1]
+            .cast(Dtype::ColDouble, CastOptions::lenient())
+            .unwrap();
+        assert_eq!(b.dtype(), Dtype::ColDoubleNullable);
+        assert_eq!(b.data.to_string(), "2.5, NA, ");
+        assert!(b.cast(Dtype::ColF64, CastOptions::strict()).is_err());
+
+        let c = df.data[2]
+            .cast(Dtype::ColU64Nullable, CastOptions::lenient())
+            .unwrap();
+        assert_eq!(c.data.to_string(), "NA, 9007199254740993, ");
+        let big = csv_read_str_iter("1e40", "x:f64").unwrap();
+        assert!(big.data[0]
+            .cast(Dtype::ColDouble, CastOptions::strict())
+            .is_err());
+    }
+
+    #[test]
+    fn cast_lenient_to_any_dtype() {
+        let df = csv_read_str_iter("[1],ff\nx,61", "s:string,b:binary").unwrap();
+        let l = df.data[0]
+            .cast(Dtype::ColList, CastOptions::lenient())
+            .unwrap();
+        assert_eq!(l.dtype(), Dtype::ColListNullable);
+        assert_eq!(l.data.get_value(1), Value::Null);
+        assert!(df.data[0]
+            .cast(Dtype::ColList, CastOptions::strict())
+            .is_err());
+
+        //invalid utf8 is no category
+        let c = df.data[1]
+            .cast(Dtype::ColCategorical, CastOptions::lenient())
+            .unwrap();
+        assert_eq!(c.dtype(), Dtype::ColCategoricalNullable);
+        assert_eq!(c.data.to_string(), "NA, a, ");
+    }
+
+    #[test]
+    fn cast_copies_bools_and_strings() {
+        let myschema = "b:bool[true=Y;false=N],s:string,n:boolNullable[true=Y;false=N]";
+        let mut df = csv_read_str_iter("Y,,N\nN,x,", myschema).unwrap();
+        df.data[0].metadata.insert("unit".into(), "flag".into());
+
+        //bool tokens, empty strings and metadata are kept
+        let b = df.data[0]
+            .cast(Dtype::ColBoolNullable, CastOptions::strict())
+            .unwrap();
+        assert_eq!(b.data.to_string(), "true, false, ");
+        assert_eq!(b.data.type_args(), df.data[0].data.type_args());
+        assert_eq!(b.metadata["unit"], "flag");
+        let n = df.data[2]
+            .cast(Dtype::ColBool, CastOptions::lenient())
+            .unwrap();
+        assert_eq!(n.dtype(), Dtype::ColBoolNullable);
+        assert_eq!(n.data.get_value(0), Value::Bool(false));
+        assert!(df.data[2]
+            .cast(Dtype::ColBool, CastOptions::strict())
+            .is_err());
+        let s = df.data[1]
+            .cast(Dtype::ColStringPoolNullable, CastOptions::strict())
+            .unwrap();
+        assert_eq!(s.data.get_value(0), Value::Str(""));
+        assert_eq!(s.data.null_count(), 0);
+    }
+
+    #[test]
+    fn cast_temporal_and_decimal() {
+        let myschema = "ts:datetime[unit=ms],amount:decimal[6,2],flag:bool";
+        let df = csv_read_str_iter("2021-03-04 05:06:07.5,12.50,true", myschema).unwrap();
+
+        let d = df.data[0]
+            .cast(Dtype::ColDate, CastOptions::strict())
+            .unwrap();
+        assert_eq!(d.data.to_string(), "2021-03-04, ");
+        let us = Dtype::ColDatetime(TimeUnit::Microseconds);
+        let ts = df.data[0].cast(us, CastOptions::strict()).unwrap();
+        assert_eq!(
+            ts.data.get_value(0),
+            Value::Datetime(1614834367500000, TimeUnit::Microseconds)
+        );
+
+        let f = df.data[1]
+            .cast(Dtype::ColF64, CastOptions::strict())
+            .unwrap();
+        assert_eq!(f.as_f64().unwrap(), &[12.5]);
+        assert!(df.data[1]
+            .cast(Dtype::ColI64, CastOptions::strict())
+            .is_err());
+        let back = f
+            .cast(Dtype::ColDecimal(6, 1), CastOptions::strict())
+            .unwrap();
+        assert_eq!(back.data.to_string(), "12.5, ");
+
+        //ints outside the calendar range do not cast
+        let days = csv_read_str_iter("2000000000\n9223372036854775807", "n:i64").unwrap();
+        assert!(days.data[0]
+            .cast(Dtype::ColDate, CastOptions::strict())
+            .is_err());
+        let d = days.data[0]
+            .cast(Dtype::ColDate, CastOptions::lenient())
+            .unwrap();
+        assert_eq!(d.data.to_string(), "NA, NA, ");
+        let ms = Dtype::ColDatetime(TimeUnit::Milliseconds);
+        assert!(days.data[0].cast(ms, CastOptions::strict()).is_err());
+        let ts = days.data[0].cast(ms, CastOptions::lenient()).unwrap();
+        assert_eq!(
+            ts.data.get_value(0),
+            Value::Datetime(2000000000, TimeUnit::Milliseconds)
+        );
+        assert_eq!(ts.data.get_value(1), Value::Null);
+
+        let n = df.data[2]
+            .cast(Dtype::ColU32, CastOptions::strict())
+            .unwrap();
+        assert_eq!(n.as_u32().unwrap(), &[1]);
+        assert!(df.data[2]
+            .cast(
+                Dtype::ColDuration(TimeUnit::Microseconds),
+                CastOptions::strict()
+            )
+            .is_err());
+    }
+}
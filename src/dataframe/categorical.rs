@@ -0,0 +1,364 @@
+use super::column::{extend_source, ColBool, ColError, ColErrorcode, Dtype, Value, VectorData};
+use crate::bitmap::Bitmap;
+use crate::stringpool::StringPool;
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::collections::HashMap;
+use std::error;
+
+type Result<T> = std::result::Result<T, Box<dyn error::Error>>;
+
+//code of null rows, never a category
+pub const NULL_CODE: u32 = u32::MAX;
+
+//dictionary encoded strings, each distinct string is stored once in categories
+//and rows hold the u32 code of their category. categoricalNullable has a validity
+//bitmap, its null rows hold NULL_CODE
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+#[serde(from = "StoredCategorical")]
+pub struct ColCategorical {
+    codes: Vec<u32>,
+    categories: StringPool,
+    //code by category, not serialized but rebuilt from categories
+    #[serde(skip)]
+    lookup: HashMap<String, u32>,
+    #[serde(default)]
+    validity: Option<Bitmap>,
+}
+
+//the serialized fields of ColCategorical
+#[derive(Deserialize)]
+struct StoredCategorical {
+    codes: Vec<u32>,
+    categories: StringPool,
+    #[serde(default)]
+    validity: Option<Bitmap>,
+}
+
+impl From<StoredCategorical> for ColCategorical {
+    fn from(stored: StoredCategorical) -> ColCategorical {
+        let lookup = (0..stored.categories.len())
+            .map(|i| (stored.categories.get_str(i).to_string(), i as u32))
+            .collect();
+        ColCategorical {
+            codes: stored.codes,
+            categories: stored.categories,
+            lookup,
+            validity: stored.validity,
+        }
+    }
+}
+
+impl ColCategorical {
+    pub fn new() -> ColCategorical {
+        ColCategorical::default()
+    }
+
+    //as self, but able to hold nulls
+    pub fn with_nulls(mut self) -> ColCategorical {
+        if self.validity.is_none() {
+            self.validity = Some(self.codes.iter().map(|_| true).collect());
+        }
+        self
+    }
+
+    //code of s, adding s to the dictionary if new
+    fn intern(&mut self, s: &str) -> u32 {
+        if let Some(code) = self.lookup.get(s) {
+            return *code;
+        }
+        let code = self.categories.len() as u32;
+        self.categories.add_str(s);
+        self.lookup.insert(s.to_string(), code);
+        code
+    }
+
+    pub fn push_str(&mut self, s: &str) {
+        let code = self.intern(s);
+        self.codes.push(code);
+        if let Some(v) = self.validity.as_mut() {
+            v.push(true);
+        }
+    }
+
+    //an error if the column is not nullable, see with_nulls
+    pub fn push_null(&mut self) -> Result<()> {
+        let Some(v) = self.validity.as_mut() else {
+            return Err(null_err());
+        };
+        v.push(false);
+        self.codes.push(NULL_CODE);
+        Ok(())
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&str> {
+        match self.codes[idx] {
+            NULL_CODE => None,
+            code => Some(self.categories.get_str(code as usize)),
+        }
+    }
+
+    //"" for null rows
+    pub fn get_str(&self, idx: usize) -> &str {
+        self.get(idx).unwrap_or("")
+    }
+
+    pub fn codes(&self) -> &[u32] {
+        &self.codes
+    }
+
+    pub fn code_of(&self, s: &str) -> Option<u32> {
+        self.lookup.get(s).copied()
+    }
+
+    pub fn n_categories(&self) -> usize {
+        self.categories.len()
+    }
+
+    pub fn categories(&self) -> Vec<&str> {
+        (0..self.categories.len())
+            .map(|i| self.categories.get_str(i))
+            .collect()
+    }
+
+    //rebuild the dictionary in the given order, every used category must be listed
+    pub fn recode(&mut self, categories: &[&str]) -> Result<()> {
+        let mut new_dict = ColCategorical::new();
+        categories.iter().for_each(|s| {
+            new_dict.intern(s);
+        });
+        let remap: Vec<Option<u32>> = self
+            .categories()
+            .iter()
+            .map(|s| new_dict.code_of(s))
+            .collect();
+
+        //unused categories may be dropped, used ones may not
+        for code in self.codes.iter().filter(|c| **c != NULL_CODE) {
+            if remap[*code as usize].is_none() {
+                Err(ColError {
+                    errorcode: ColErrorcode::ParseValue,
+                    error_msg: format!(
+                        "category \"{}\" is missing in recode",
+                        self.categories.get_str(*code as usize)
+                    ),
+                })?;
+            }
+        }
+        let remap: Vec<u32> = remap.iter().map(|x| x.unwrap_or(0)).collect();
+        new_dict.codes = self.codes.iter().map(|c| remap_code(&remap, *c)).collect();
+        new_dict.validity = self.validity.take();
+        *self = new_dict;
+        Ok(())
+    }
+
+    //append other, its categories are merged into this dictionary. nulls of other are an
+    //error if self is not nullable
+    pub fn extend_from(&mut self, other: &ColCategorical) -> Result<()> {
+        if self.validity.is_none() && other.null_count() > 0 {
+            return Err(null_err());
+        }
+        let remap: Vec<u32> = other.categories().iter().map(|s| self.intern(s)).collect();
+        self.codes
+            .extend(other.codes.iter().map(|c| remap_code(&remap, *c)));
+        if let Some(v) = self.validity.as_mut() {
+            match &other.validity {
+                Some(o) => v.extend(o),
+                None => other.codes.iter().for_each(|_| v.push(true)),
+            }
+        }
+        Ok(())
+    }
+
+    pub fn concat(&self, other: &ColCategorical) -> Result<ColCategorical> {
+        let mut out = self.clone();
+        out.extend_from(other)?;
+        Ok(out)
+    }
+
+    //mask of rows equal to s, compares codes only
+    pub fn eq_mask(&self, s: &str) -> ColBool {
+        let values: Vec<bool> = match self.code_of(s) {
+            Some(code) => self.codes.iter().map(|c| *c == code).collect(),
+            None => vec![false; self.codes.len()],
+        };
+        ColBool::from_bools(&values)
+    }
+
+    //row indices per category, indexed by code, and the null rows last
+    pub fn group_indices(&self) -> Vec<Vec<usize>> {
+        let n = self.categories.len();
+        let mut groups = vec![Vec::new(); n + 1];
+        for (i, code) in self.codes.iter().enumerate() {
+            match *code {
+                NULL_CODE => groups[n].push(i),
+                code => groups[code as usize].push(i),
+            }
+        }
+        groups
+    }
+
+    //rows per category, indexed by code
+    pub fn category_counts(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.categories.len()];
+        self.codes
+            .iter()
+            .filter(|c| **c != NULL_CODE)
+            .for_each(|c| counts[*c as usize] += 1);
+        counts
+    }
+}
+
+fn null_err() -> Box<dyn error::Error> {
+    Box::new(ColError {
+        errorcode: ColErrorcode::ParseValue,
+        error_msg: String::from("null in a non-nullable categorical"),
+    })
+}
+
+fn remap_code(remap: &[u32], code: u32) -> u32 {
+    match code {
+        NULL_CODE => NULL_CODE,
+        _ => remap[code as usize],
+    }
+}
+
+#[typetag::serde]
+impl VectorData for ColCategorical {
+    //empty cells are null in categoricalNullable
+    fn push_from_str(&mut self, x: &str) -> Result<()> {
+        if x.is_empty() && self.validity.is_some() {
+            self.push_null()?;
+        } else {
+            self.push_str(x);
+        }
+        Ok(())
+    }
+    fn to_string(&self) -> String {
+        (0..self.codes.len())
+            .map(|i| self.get(i).unwrap_or("NA"))
+            .fold(String::new(), |a, b| a + b + ", ")
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn reserve(&mut self, additional: usize) {
+        self.codes.reserve(additional);
+        if let Some(v) = self.validity.as_mut() {
+            v.reserve(additional);
+        }
+    }
+    //the dictionary is kept as is, like in take
+    fn truncate(&mut self, len: usize) {
+        self.codes.truncate(len);
+        if let Some(v) = self.validity.as_mut() {
+            v.truncate(len);
+        }
+    }
+    fn boxed_clone(&self) -> Box<dyn VectorData> {
+        Box::new(self.clone())
+    }
+    fn dtype(&self) -> Dtype {
+        match self.validity {
+            Some(_) => Dtype::ColCategoricalNullable,
+            None => Dtype::ColCategorical,
+        }
+    }
+    fn len(&self) -> usize {
+        self.codes.len()
+    }
+    fn get_value(&self, idx: usize) -> Value<'_> {
+        self.get(idx).map_or(Value::Null, Value::Str)
+    }
+    fn take(&self, indices: &[usize]) -> Box<dyn VectorData> {
+        //the dictionary is kept as is, unused categories included
+        Box::new(ColCategorical {
+            codes: indices.iter().map(|i| self.codes[*i]).collect(),
+            categories: self.categories.clone(),
+            lookup: self.lookup.clone(),
+            validity: self.validity.as_ref().map(|v| v.take(indices)),
+        })
+    }
+    fn validity(&self) -> Option<&Bitmap> {
+        self.validity.as_ref()
+    }
+    fn extend(&mut self, other: &dyn VectorData) -> Result<()> {
+        let other: &Self = extend_source(other, self.dtype())?;
+        self.extend_from(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_strs(values: &[&str]) -> ColCategorical {
+        let mut col = ColCategorical::new();
+        values.iter().for_each(|s| col.push_str(s));
+        col
+    }
+
+    #[test]
+    fn interns_categories() {
+        let col = from_strs(&["b", "a", "b", "b", "c"]);
+        assert_eq!(col.categories(), vec!["b", "a", "c"]);
+        assert_eq!(col.codes(), &[0, 1, 0, 0, 2]);
+        assert_eq!(col.get_str(4), "c");
+        assert_eq!(col.category_counts(), vec![3, 1, 1]);
+        assert_eq!(col.group_indices()[0], vec![0, 2, 3]);
+        assert!(col.group_indices()[3].is_empty());
+        assert_eq!(col.eq_mask("b").true_indices(), vec![0, 2, 3]);
+        assert!(col.eq_mask("z").true_indices().is_empty());
+    }
+
+    #[test]
+    fn recode_and_concat() {
+        let mut col = from_strs(&["b", "a", "b"]);
+        col.recode(&["a", "b", "unused"]).unwrap();
+        assert_eq!(col.categories(), vec!["a", "b", "unused"]);
+        assert_eq!(col.codes(), &[1, 0, 1]);
+        assert!(col.recode(&["a"]).is_err());
+
+        let other = from_strs(&["c", "a"]);
+        let both = col.concat(&other).unwrap();
+        assert_eq!(both.categories(), vec!["a", "b", "unused", "c"]);
+        assert_eq!(both.codes(), &[1, 0, 1, 3, 0]);
+        assert_eq!(VectorData::to_string(&both), "b, a, b, c, a, ");
+    }
+
+    #[test]
+    fn nullable_categories() {
+        let mut col = ColCategorical::new().with_nulls();
+        ["b", "", "a", "b"]
+            .iter()
+            .for_each(|s| col.push_from_str(s).unwrap());
+        assert_eq!(col.dtype(), Dtype::ColCategoricalNullable);
+        assert_eq!(col.categories(), vec!["b", "a"]);
+        assert_eq!(col.null_count(), 1);
+        assert_eq!(col.get_value(1), Value::Null);
+        assert_eq!(col.category_counts(), vec![2, 1]);
+        assert_eq!(col.eq_mask("b").true_indices(), vec![0, 3]);
+
+        col.recode(&["a", "b"]).unwrap();
+        assert_eq!(VectorData::to_string(&col), "b, NA, a, b, ");
+
+        assert_eq!(col.group_indices()[2], vec![1]);
+
+        //nulls are an error in a non-nullable column, until it is made nullable
+        let mut c = from_strs(&["c"]);
+        assert!(c.push_null().is_err());
+        assert!(c.concat(&col).is_err());
+        assert_eq!(c.dtype(), Dtype::ColCategorical);
+        let both = c.with_nulls().concat(&col).unwrap();
+        assert_eq!(both.dtype(), Dtype::ColCategoricalNullable);
+        assert_eq!(VectorData::to_string(&both), "c, b, NA, a, b, ");
+        assert_eq!(VectorData::to_string(&*both.take(&[2, 0])), "NA, c, ");
+
+        //the lookup is rebuilt after deserializing
+        let mut back: ColCategorical =
+            serde_json::from_str(&serde_json::to_string(&both).unwrap()).unwrap();
+        assert_eq!(back, both);
+        back.push_str("a");
+        assert_eq!(back.n_categories(), both.n_categories());
+    }
+}
@@ -0,0 +1,343 @@
+use super::column::{CastOptions, Column, Dtype, TimeUnit};
+use super::{DFError, DataFrame, Result};
+use std::collections::HashSet;
+
+//what to do with a column that only one of the stacked frames has
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ColumnPolicy {
+    #[default]
+    Error,
+    FillNull, //the rows of the frame without the column are null, the column becomes nullable
+    Drop,
+}
+
+//missing are columns of the first frame the appended frame lacks, extra are columns
+//only the appended frame has
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct ConcatOptions {
+    pub missing: ColumnPolicy,
+    pub extra: ColumnPolicy,
+}
+
+impl ConcatOptions {
+    pub fn fill_null() -> ConcatOptions {
+        ConcatOptions {
+            missing: ColumnPolicy::FillNull,
+            extra: ColumnPolicy::FillNull,
+        }
+    }
+}
+
+fn concat_err(error_msg: String) -> Box<dyn std::error::Error> {
+    Box::new(DFError {
+        error_msg,
+        sub_errors: Vec::new(),
+    })
+}
+
+//signedness and bits of the numeric dtypes, floats are 'f'
+pub(crate) fn numeric_kind(dtype: Dtype) -> Option<(char, u8)> {
+    match dtype {
+        Dtype::ColInt => Some(('i', 32)),
+        Dtype::ColI64 => Some(('i', 64)),
+        Dtype::ColU32 => Some(('u', 32)),
+        Dtype::ColU64 => Some(('u', 64)),
+        Dtype::ColDouble => Some(('f', 32)),
+        Dtype::ColF64 => Some(('f', 64)),
+        _ => None,
+    }
+}
+
+fn numeric_supertype(a: (char, u8), b: (char, u8)) -> Option<Dtype> {
+    let dtype = match (a, b) {
+        //ints only fit f64 without loss, f32 has 24 bits of mantissa
+        (('f', x), ('f', y)) => match x.max(y) {
+            32 => Dtype::ColDouble,
+            _ => Dtype::ColF64,
+        },
+        (('f', _), _) | (_, ('f', _)) => Dtype::ColF64,
+        (('i', x), ('i', y)) => match x.max(y) {
+            32 => Dtype::ColInt,
+            _ => Dtype::ColI64,
+        },
+        (('u', x), ('u', y)) => match x.max(y) {
+            32 => Dtype::ColU32,
+            _ => Dtype::ColU64,
+        },
+        //signed and unsigned meet in i64, which does not hold all u64
+        (('u', 64), _) | (_, ('u', 64)) => return None,
+        _ => Dtype::ColI64,
+    };
+    Some(dtype)
+}
+
+//the dtype both dtypes cast to without loss, e.g. int and doubleNullable give doubleNullable
+pub fn supertype(a: Dtype, b: Dtype) -> Option<Dtype> {
+    if a == b {
+        return Some(a);
+    }
+    let nullable = a.is_nullable() || b.is_nullable();
+    let (a, b) = (a.non_nullable(), b.non_nullable());
+    let base = match (a, b) {
+        _ if a == b => a,
+        (Dtype::ColString | Dtype::ColStringPool, Dtype::ColString | Dtype::ColStringPool) => {
+            Dtype::ColString
+        }
+        (Dtype::ColDecimal(p1, s1), Dtype::ColDecimal(p2, s2)) => {
+            let scale = s1.max(s2);
+            let int_digits = (p1 - s1).max(p2 - s2);
+            let precision = (int_digits + scale).min(super::decimal::MAX_PRECISION);
+            Dtype::ColDecimal(precision, scale)
+        }
+        //the finer unit holds both
+        (Dtype::ColDatetime(u1), Dtype::ColDatetime(u2)) => Dtype::ColDatetime(finer(u1, u2)),
+        (Dtype::ColDuration(u1), Dtype::ColDuration(u2)) => Dtype::ColDuration(finer(u1, u2)),
+        (Dtype::ColDate, Dtype::ColDatetime(u)) | (Dtype::ColDatetime(u), Dtype::ColDate) => {
+            Dtype::ColDatetime(u)
+        }
+        _ => numeric_supertype(numeric_kind(a)?, numeric_kind(b)?)?,
+    };
+    Some(if nullable { base.nullable() } else { base })
+}
+
+fn finer(u1: TimeUnit, u2: TimeUnit) -> TimeUnit {
+    if u1.ticks_per_second() >= u2.ticks_per_second() {
+        u1
+    } else {
+        u2
+    }
+}
+
+//rows of a below rows of b, a keeps its metadata, options and constraints
+pub(crate) fn stack_columns(a: Column, b: &Column) -> Result<Column> {
+    let name = a.name.clone().unwrap_or_default();
+    let dtype = supertype(a.dtype(), b.dtype()).ok_or_else(|| {
+        concat_err(format!(
+            "column \"{}\" cannot stack {} and {}",
+            name,
+            a.dtype(),
+            b.dtype()
+        ))
+    })?;
+    let mut out = if a.dtype() == dtype {
+        a
+    } else {
+        let mut out = a.cast(dtype, CastOptions::strict())?;
+        out.metadata = a.metadata;
+        out.options = a.options;
+        out.constraints = a.constraints;
+        out
+    };
+    if b.dtype() == dtype {
+        out.data.extend(b.data.as_ref())?;
+    } else {
+        let b = b.cast(dtype, CastOptions::strict())?;
+        out.data.extend(b.data.as_ref())?;
+    }
+    Ok(out)
+}
+
+//nrow nulls in the nullable variant of the dtype of like, with its type arguments
+pub(crate) fn null_column(like: &Column, nrow: usize) -> Result<Column> {
+    let dtype = like.dtype().nullable();
+    let mut out = Column::new_with_args(like.name.clone(), dtype, &like.data.type_args())?;
+    out.data.reserve(nrow);
+    for _ in 0..nrow {
+        out.data.push_from_str("")?;
+    }
+    Ok(out)
+}
+
+//stacking aligns by name, so every column needs a distinct name
+fn check_names(df: &DataFrame) -> Result<()> {
+    let mut seen = HashSet::new();
+    for (i, col) in df.data.iter().enumerate() {
+        match &col.name {
+            None => return Err(concat_err(format!("column No. {} has no name", i))),
+            Some(name) if !seen.insert(name) => {
+                return Err(concat_err(format!(
+                    "column name \"{}\" is not unique",
+                    name
+                )))
+            }
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+impl DataFrame {
+    //rows of other below the rows of self, columns aligned by name and upcast to a common dtype,
+    //e.g. int and intNullable stack to intNullable
+    pub fn vstack(&self, other: &DataFrame, options: &ConcatOptions) -> Result<DataFrame> {
+        self.clone().vstack_owned(other, options)
+    }
+
+    //all frames stacked in order, the first frame gives the column order
+    pub fn concat(frames: &[DataFrame], options: &ConcatOptions) -> Result<DataFrame> {
+        let (first, rest) = frames
+            .split_first()
+            .ok_or_else(|| concat_err(String::from("nothing to concat")))?;
+        rest.iter()
+            .try_fold(first.clone(), |acc, df| acc.vstack_owned(df, options))
+    }
+
+    //columns of self followed by the columns of other, which needs the same number of rows
+    //and other column names
+    pub fn hstack(&self, other: &DataFrame) -> Result<DataFrame> {
+        let (nrow_self, nrow_other) = (self.shape().0, other.shape().0);
+        if !self.data.is_empty() && !other.data.is_empty() && nrow_self != nrow_other {
+            return Err(concat_err(format!(
+                "cannot hstack {} rows and {} rows",
+                nrow_self, nrow_other
+            )));
+        }
+        let mut data = self.data.clone();
+        data.extend(other.data.iter().cloned());
+        DataFrame::from_columns(data)
+    }
+
+    //as vstack, extending the columns of self in place
+    fn vstack_owned(self, other: &DataFrame, options: &ConcatOptions) -> Result<DataFrame> {
+        check_names(&self)?;
+        check_names(other)?;
+        let (nrow_self, nrow_other) = (self.shape().0, other.shape().0);
+        let self_names: HashSet<String> = self.data.iter().flat_map(|c| c.name.clone()).collect();
+
+        let mut data = Vec::with_capacity(self.data.len());
+        for col in self.data.into_iter() {
+            let name = col.name.clone().unwrap_or_default();
+            match other.position_of(&name) {
+                Ok(j) => data.push(stack_columns(col, &other.data[j])?),
+                Err(_) => match options.missing {
+                    ColumnPolicy::Error => {
+                        return Err(concat_err(format!(
+                            "column \"{}\" is missing in the appended frame",
+                            name
+                        )))
+                    }
+                    ColumnPolicy::FillNull => {
+                        let nulls = null_column(&col, nrow_other)?;
+                        data.push(stack_columns(col, &nulls)?);
+                    }
+                    ColumnPolicy::Drop => (),
+                },
+            }
+        }
+
+        let extra = other
+            .data
+            .iter()
+            .filter(|c| !self_names.contains(c.name.as_deref().unwrap_or_default()));
+        for col in extra {
+            let name = col.name.as_deref().unwrap_or_default();
+            match options.extra {
+                ColumnPolicy::Error => {
+                    return Err(concat_err(format!(
+                        "column \"{}\" of the appended frame is not in the first frame",
+                        name
+                    )))
+                }
+                ColumnPolicy::FillNull => {
+                    let mut out = stack_columns(null_column(col, nrow_self)?, col)?;
+                    out.metadata = col.metadata.clone();
+                    out.options = col.options.clone();
+                    data.push(out);
+                }
+                ColumnPolicy::Drop => (),
+            }
+        }
+        DataFrame::from_columns(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataframe::csv_read_str_iter;
+
+    #[test]
+    fn supertypes() {
+        assert_eq!(
+            supertype(Dtype::ColInt, Dtype::ColIntNullable),
+            Some(Dtype::ColIntNullable)
+        );
+        assert_eq!(
+            supertype(Dtype::ColIntNullable, Dtype::ColDouble),
+            Some(Dtype::ColF64Nullable)
+        );
+        assert_eq!(supertype(Dtype::ColU32, Dtype::ColInt), Some(Dtype::ColI64));
+        assert_eq!(
+            supertype(Dtype::ColI64, Dtype::ColDouble),
+            Some(Dtype::ColF64)
+        );
+        assert_eq!(supertype(Dtype::ColU64, Dtype::ColInt), None);
+        assert_eq!(
+            supertype(Dtype::ColDecimal(6, 2), Dtype::ColDecimalNullable(10, 4)),
+            Some(Dtype::ColDecimalNullable(10, 4))
+        );
+        assert_eq!(
+            supertype(Dtype::ColStringPool, Dtype::ColStringNullable),
+            Some(Dtype::ColStringNullable)
+        );
+        assert_eq!(supertype(Dtype::ColString, Dtype::ColInt), None);
+    }
+
+    #[test]
+    fn vstack_by_name() {
+        let df1 = csv_read_str_iter("1,a\n2,b", "id:int,name:stringPool").unwrap();
+        let df2 =
+            csv_read_str_iter("c,3,0.5\nd,,1.5", "name:string,id:intNullable,x:double").unwrap();
+
+        //the extra column x is an error by default
+        assert!(df1.vstack(&df2, &ConcatOptions::default()).is_err());
+
+        let options = ConcatOptions {
+            extra: ColumnPolicy::Drop,
+            ..ConcatOptions::default()
+        };
+        let df = df1.vstack(&df2, &options).unwrap();
+        assert_eq!(df.shape(), (4, 2));
+        assert_eq!(df.data[0].dtype(), Dtype::ColIntNullable);
+        assert_eq!(df.data[0].data.to_string(), "1, 2, 3, NA, ");
+        assert_eq!(df.data[1].dtype(), Dtype::ColString);
+        assert_eq!(df.data[1].data.to_string(), "a, b, c, d, ");
+
+        let df = DataFrame::concat(
+            &[df1.clone(), df2.clone(), df1],
+            &ConcatOptions::fill_null(),
+        )
+        .unwrap();
+        assert_eq!(df.shape(), (6, 3));
+        assert_eq!(df.data[2].dtype(), Dtype::ColDoubleNullable);
+        assert_eq!(df.data[2].data.to_string(), "NA, NA, 0.5, 1.5, NA, NA, ");
+
+        let df3 = csv_read_str_iter("x", "name:string").unwrap();
+        assert!(df2.vstack(&df3, &ConcatOptions::default()).is_err());
+        let df = df2
+            .vstack(
+                &df3,
+                &ConcatOptions {
+                    missing: ColumnPolicy::Drop,
+                    ..ConcatOptions::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(df.shape(), (3, 1));
+    }
+
+    #[test]
+    fn hstack_columns() {
+        let df1 = csv_read_str_iter("1,a\n2,b", "id:int,name:stringPool").unwrap();
+        let df2 = csv_read_str_iter("0.5\n1.5", "x:double").unwrap();
+        let df = df1.hstack(&df2).unwrap();
+        assert_eq!(df.names(), vec![Some("id"), Some("name"), Some("x")]);
+        assert_eq!(df.column("x").unwrap().data.to_string(), "0.5, 1.5, ");
+
+        assert!(df1.hstack(&df1).is_err());
+        let df3 = csv_read_str_iter("0.5", "y:double").unwrap();
+        assert!(df1.hstack(&df3).is_err());
+        let empty = DataFrame::new("").unwrap();
+        assert_eq!(empty.hstack(&df3).unwrap().shape(), (1, 1));
+    }
+}
@@ -0,0 +1,388 @@
+use super::column::{Column, Value, VectorData};
+use super::schema::Field;
+use super::{DFError, DataFrame, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::error;
+use std::fmt;
+use std::ops::Range;
+
+//Constraint is a rule the cells of a column keep, bounds and allowed values are
+//text parsed like cells of the column, nulls only break NotNull
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Constraint {
+    NotNull,
+    Unique,
+    Min(String), //inclusive
+    Max(String), //inclusive
+    OneOf(Vec<String>),
+    Matches(String), //regex, checked on the cell text
+}
+
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Constraint::NotNull => write!(f, "not null"),
+            Constraint::Unique => write!(f, "unique"),
+            Constraint::Min(x) => write!(f, "min {}", x),
+            Constraint::Max(x) => write!(f, "max {}", x),
+            Constraint::OneOf(x) => write!(f, "one of [{}]", x.join(", ")),
+            Constraint::Matches(x) => write!(f, "matches {}", x),
+        }
+    }
+}
+
+//Constraints are the constraints by column name, e.g.
+//Constraints::new().add("id", Constraint::Unique).add("age", Constraint::Min("0".into()))
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Constraints {
+    pub rules: Vec<(String, Constraint)>,
+}
+
+impl Constraints {
+    pub fn new() -> Constraints {
+        Constraints::default()
+    }
+
+    pub fn add(mut self, column: &str, constraint: Constraint) -> Constraints {
+        self.rules.push((column.to_string(), constraint));
+        self
+    }
+}
+
+//Violation is one broken constraint with the rows breaking it
+#[derive(Clone, Debug, PartialEq)]
+pub struct Violation {
+    pub column: String,
+    pub constraint: Constraint,
+    pub rows: Vec<usize>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ValidationReport {
+    pub violations: Vec<Violation>,
+}
+
+impl ValidationReport {
+    pub fn is_ok(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} constraint violations", self.violations.len())?;
+        for v in self.violations.iter() {
+            write!(
+                f,
+                "\ncolumn \"{}\" {}: rows {:?}",
+                v.column, v.constraint, v.rows
+            )?;
+        }
+        Ok(())
+    }
+}
+impl error::Error for ValidationReport {}
+
+//UniqueSeen holds the values of the first rows of a column with a unique constraint, so
+//appends only look at the new rows
+#[derive(Clone, Debug, Default)]
+pub(crate) struct UniqueSeen {
+    rows: usize,
+    values: HashSet<String>,
+}
+
+//key of a cell for unique, the same for equal values
+fn cell_key(data: &dyn VectorData, idx: usize) -> String {
+    data.get_value(idx).to_string()
+}
+
+//non-null rows in range with their keys
+fn cell_keys(data: &dyn VectorData, rows: Range<usize>) -> Vec<(usize, String)> {
+    rows.filter(|i| !data.is_null(*i))
+        .map(|i| (i, cell_key(data, i)))
+        .collect()
+}
+
+//the values of rows before nrow, rebuilt if the column changed since they were taken
+fn seen_values<'a>(
+    seen: &'a mut Option<UniqueSeen>,
+    data: &dyn VectorData,
+    nrow: usize,
+) -> &'a mut UniqueSeen {
+    if seen.as_ref().is_none_or(|s| s.rows != nrow) {
+        *seen = Some(UniqueSeen {
+            rows: nrow,
+            values: cell_keys(data, 0..nrow)
+                .into_iter()
+                .map(|(_, k)| k)
+                .collect(),
+        });
+    }
+    seen.as_mut().unwrap()
+}
+
+//rows in range whose value is seen before or comes more than once in range
+fn appended_duplicates(seen: &UniqueSeen, data: &dyn VectorData, rows: Range<usize>) -> Vec<usize> {
+    let keys = cell_keys(data, rows);
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for (_, k) in keys.iter() {
+        *counts.entry(k).or_default() += 1;
+    }
+    keys.iter()
+        .filter(|(_, k)| seen.values.contains(k) || counts[&k[..]] > 1)
+        .map(|(i, _)| *i)
+        .collect()
+}
+
+//cells of text parsed by an empty column like col, nullable columns parse bad text to null
+fn parse_like(col: &Column, texts: &[String]) -> Result<Column> {
+    let mut out = Field::from_column(col).to_column()?;
+    for (i, x) in texts.iter().enumerate() {
+        out.data.push_from_str(x)?;
+        if out.data.is_null(i) {
+            Err(DFError {
+                error_msg: format!(
+                    "constraint value \"{}\" does not parse as {}",
+                    x,
+                    col.dtype()
+                ),
+                sub_errors: Vec::new(),
+            })?;
+        }
+    }
+    Ok(out)
+}
+
+//rows in range breaking the constraint, unique looks at all rows for duplicates
+fn violating_rows(col: &Column, constraint: &Constraint, rows: Range<usize>) -> Result<Vec<usize>> {
+    let data = col.data.as_ref();
+    let non_null = || rows.clone().filter(|i| !data.is_null(*i));
+    let out = match constraint {
+        Constraint::NotNull => rows.clone().filter(|i| data.is_null(*i)).collect(),
+        Constraint::Unique => {
+            let mut seen: HashMap<String, usize> = HashMap::new();
+            for (_, k) in cell_keys(data, 0..data.len()) {
+                *seen.entry(k).or_default() += 1;
+            }
+            non_null()
+                .filter(|i| seen[&cell_key(data, *i)] > 1)
+                .collect()
+        }
+        Constraint::Min(x) | Constraint::Max(x) => {
+            let bound_col = parse_like(col, std::slice::from_ref(x))?;
+            let bound = bound_col.data.get_value(0);
+            //cells that do not compare, like NaN, break the bound too
+            let allowed = match constraint {
+                Constraint::Min(_) => [Ordering::Greater, Ordering::Equal],
+                _ => [Ordering::Less, Ordering::Equal],
+            };
+            non_null()
+                .filter(|i| {
+                    let ord = data.get_value(*i).partial_cmp(&bound);
+                    !ord.is_some_and(|o| allowed.contains(&o))
+                })
+                .collect()
+        }
+        Constraint::OneOf(x) => {
+            let allowed_col = parse_like(col, x)?;
+            let allowed: Vec<Value> = (0..x.len())
+                .map(|j| allowed_col.data.get_value(j))
+                .collect();
+            non_null()
+                .filter(|i| !allowed.contains(&data.get_value(*i)))
+                .collect()
+        }
+        Constraint::Matches(x) => {
+            let re = Regex::new(x)?;
+            non_null()
+                .filter(|i| match data.get_value(*i) {
+                    Value::Str(s) => !re.is_match(s),
+                    value => !re.is_match(&value.to_string()),
+                })
+                .collect()
+        }
+    };
+    Ok(out)
+}
+
+impl DataFrame {
+    //report of all rows breaking the constraints, an error if a constraint names no column
+    //or does not parse, e.g. a bad regex
+    pub fn validate(&self, constraints: &Constraints) -> Result<ValidationReport> {
+        let (nrow, _) = self.shape();
+        let mut report = ValidationReport::default();
+        for (name, constraint) in constraints.rules.iter() {
+            let col = self.column(name)?;
+            let rows = violating_rows(col, constraint, 0..nrow)?;
+            if !rows.is_empty() {
+                report.violations.push(Violation {
+                    column: name.clone(),
+                    constraint: constraint.clone(),
+                    rows,
+                });
+            }
+        }
+        Ok(report)
+    }
+
+    //keep the constraints on the columns, append_line and append_str then reject
+    //input that breaks them
+    pub fn set_constraints(&mut self, constraints: &Constraints) -> Result<()> {
+        //unknown columns and bad bounds or regexes fail here rather than on append
+        self.validate(constraints)?;
+        for (name, constraint) in constraints.rules.iter() {
+            let i = self.position_of(name)?;
+            self.data[i].constraints.push(constraint.clone());
+        }
+        Ok(())
+    }
+
+    //violations of the constraints kept on the columns by the rows from nrow on, unique
+    //compares them to the values seen in the rows before
+    fn check_rows(&mut self, nrow: usize) -> Result<ValidationReport> {
+        let rows = nrow..self.shape().0;
+        let mut report = ValidationReport::default();
+        for (i, col) in self.data.iter_mut().enumerate() {
+            for constraint in col.constraints.iter() {
+                let bad = match constraint {
+                    Constraint::Unique => {
+                        let seen = seen_values(&mut col.unique_seen, col.data.as_ref(), nrow);
+                        appended_duplicates(seen, col.data.as_ref(), rows.clone())
+                    }
+                    _ => violating_rows(col, constraint, rows.clone())?,
+                };
+                if !bad.is_empty() {
+                    report.violations.push(Violation {
+                        column: col.name.clone().unwrap_or_else(|| format!("col_{}", i)),
+                        constraint: constraint.clone(),
+                        rows: bad,
+                    });
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    //check rows appended from nrow on, drop them again if they break a constraint
+    pub(crate) fn check_appended(&mut self, nrow: usize) -> Result<()> {
+        if self.data.iter().all(|c| c.constraints.is_empty()) {
+            return Ok(());
+        }
+        let report = self.check_rows(nrow)?;
+        if !report.is_ok() {
+            self.truncate(nrow);
+            Err(DFError {
+                error_msg: String::from("appended rows break constraints"),
+                sub_errors: vec![Box::new(report)],
+            })?;
+        }
+        //the kept rows are seen by the next append
+        let (len, _) = self.shape();
+        for col in self.data.iter_mut() {
+            if let Some(seen) = col.unique_seen.as_mut() {
+                let keys = cell_keys(col.data.as_ref(), seen.rows..len);
+                seen.values.extend(keys.into_iter().map(|(_, k)| k));
+                seen.rows = len;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataframe::csv_read_str_iter;
+    use crate::dataframe::nested::ColList;
+
+    #[test]
+    fn validate_constraints() {
+        let mycsvstr = "1,34,de,a@x.org\n2,,fr,b@x.org\n2,-1,xx,nope\n4,130,de,";
+        let myschema = "id:int,age:intNullable,country:stringPool,mail:stringNullable";
+        let df = csv_read_str_iter(mycsvstr, myschema).unwrap();
+
+        let constraints = Constraints::new()
+            .add("id", Constraint::Unique)
+            .add("age", Constraint::NotNull)
+            .add("age", Constraint::Min(String::from("0")))
+            .add("age", Constraint::Max(String::from("120")))
+            .add(
+                "country",
+                Constraint::OneOf(vec![String::from("de"), String::from("fr")]),
+            )
+            .add("mail", Constraint::Matches(String::from(r"^\S+@\S+$")));
+        let report = df.validate(&constraints).unwrap();
+        let rows: Vec<(&str, Vec<usize>)> = report
+            .violations
+            .iter()
+            .map(|v| (&v.column[..], v.rows.clone()))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("id", vec![1, 2]),
+                ("age", vec![1]),
+                ("age", vec![2]),
+                ("age", vec![3]),
+                ("country", vec![2]),
+                ("mail", vec![2]),
+            ]
+        );
+        assert!(report
+            .to_string()
+            .starts_with("6 constraint violations\ncolumn \"id\" unique: rows [1, 2]"));
+
+        assert!(df
+            .validate(&Constraints::new().add("nope", Constraint::NotNull))
+            .is_err());
+        assert!(df
+            .validate(&Constraints::new().add("age", Constraint::Min(String::from("x"))))
+            .is_err());
+    }
+
+    #[test]
+    fn append_checks_constraints() {
+        let mut df = csv_read_str_iter("1,2021-03-04\n2,2021-03-05", "id:int,d:date").unwrap();
+        let constraints = Constraints::new()
+            .add("id", Constraint::Unique)
+            .add("d", Constraint::Min(String::from("2021-01-01")));
+        df.set_constraints(&constraints).unwrap();
+
+        df.append_line("3,2021-03-06").unwrap();
+        let err = df.append_str("4,2021-03-07\n1,2021-03-08").unwrap_err();
+        assert!(err.to_string().contains("column \"id\" unique: rows [4]"));
+        assert!(df.append_line("5,2020-12-31").is_err());
+        assert_eq!(df.shape(), (3, 2));
+        assert_eq!(df.column("id").unwrap().as_i32().unwrap(), &[1, 2, 3]);
+
+        //rows dropped again are not seen, duplicates within the new rows are
+        df.append_line("4,2021-03-09").unwrap();
+        let err = df.append_str("5,2021-03-10\n5,2021-03-11").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("column \"id\" unique: rows [4, 5]"));
+        assert!(df.append_line("4,2021-03-12").is_err());
+        df.append_line("5,2021-03-13").unwrap();
+        assert_eq!(df.column("id").unwrap().as_i32().unwrap(), &[1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn rejected_rows_are_truncated() {
+        let myschema = "id:int,s:stringPool,l:list[int],c:categoricalNullable";
+        let mut df = csv_read_str_iter("1,a,[1],x\n2,b,\"[2,3]\",", myschema).unwrap();
+        df.set_constraints(&Constraints::new().add("id", Constraint::Unique))
+            .unwrap();
+        let before = df.to_string();
+        assert!(df.append_str("3,c,[4],y\n1,d,\"[5,6]\",z").is_err());
+        assert_eq!(df.to_string(), before);
+
+        df.append_line("3,c,[4],y").unwrap();
+        assert_eq!(df.shape(), (3, 4));
+        assert_eq!(df.column("s").unwrap().data.get_value(2), Value::Str("c"));
+        let l = df.column("l").unwrap().data.as_any();
+        assert_eq!(l.downcast_ref::<ColList>().unwrap().child.len(), 4);
+    }
+}
@@ -0,0 +1,561 @@
+use super::column::{extend_source, ColError, ColErrorcode, Dtype, Value, VectorData};
+use crate::bitmap::Bitmap;
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::cmp::Ordering;
+use std::error;
+use std::fmt;
+
+type Result<T> = std::result::Result<T, Box<dyn error::Error>>;
+
+//i128 holds any 38 digit number
+pub const MAX_PRECISION: u8 = 38;
+
+//how digits beyond the target scale are rounded away
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RoundingMode {
+    #[default]
+    HalfEven,
+    HalfUp,
+    Down,
+    Up,
+    Floor,
+    Ceiling,
+}
+
+//Decimal is mantissa * 10^-scale, e.g. 12.3400 is {mantissa: 123400, scale: 4}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Decimal {
+    pub mantissa: i128,
+    pub scale: u8,
+}
+
+fn decimal_err(msg: String) -> Box<dyn error::Error> {
+    Box::new(ColError {
+        errorcode: ColErrorcode::ParseValue,
+        error_msg: msg,
+    })
+}
+
+fn pow10(n: u8) -> Option<i128> {
+    10i128.checked_pow(n as u32)
+}
+
+//num / den rounded by mode, den must be positive
+pub(crate) fn round_div(num: i128, den: i128, mode: RoundingMode) -> i128 {
+    let quot = num / den;
+    let rem = num % den;
+    if rem == 0 {
+        return quot;
+    }
+    let sign = num.signum();
+    let half_cmp = (rem.unsigned_abs() * 2).cmp(&den.unsigned_abs());
+    let away_from_zero = match mode {
+        RoundingMode::Down => false,
+        RoundingMode::Up => true,
+        RoundingMode::Floor => sign < 0,
+        RoundingMode::Ceiling => sign > 0,
+        RoundingMode::HalfUp => half_cmp != Ordering::Less,
+        RoundingMode::HalfEven => match half_cmp {
+            Ordering::Less => false,
+            Ordering::Greater => true,
+            Ordering::Equal => quot % 2 != 0,
+        },
+    };
+    if away_from_zero {
+        quot + sign
+    } else {
+        quot
+    }
+}
+
+//number of decimal digits of |x|, 0 has one digit
+fn n_digits(x: i128) -> u32 {
+    let mut x = x.unsigned_abs();
+    let mut n = 1;
+    while x >= 10 {
+        x /= 10;
+        n += 1;
+    }
+    n
+}
+
+impl Decimal {
+    pub fn new(mantissa: i128, scale: u8) -> Decimal {
+        Decimal { mantissa, scale }
+    }
+
+    //exact parse of plain decimal text like -123.4500, the scale is the number of fraction digits
+    pub fn parse(x: &str) -> Result<Decimal> {
+        let x = x.trim();
+        let err = || decimal_err(format!("cannot parse \"{}\" as decimal", x));
+        let (neg, body) = match x.as_bytes().first() {
+            Some(b'-') => (true, &x[1..]),
+            Some(b'+') => (false, &x[1..]),
+            _ => (false, x),
+        };
+        let (int_part, frac_part) = body.split_once('.').unwrap_or((body, ""));
+        if int_part.is_empty() && frac_part.is_empty() {
+            return Err(err());
+        }
+        if !int_part
+            .chars()
+            .chain(frac_part.chars())
+            .all(|c| c.is_ascii_digit())
+        {
+            return Err(err());
+        }
+        let scale = u8::try_from(frac_part.len()).map_err(|_| err())?;
+        let mut mantissa: i128 = 0;
+        for c in int_part.chars().chain(frac_part.chars()) {
+            mantissa = mantissa
+                .checked_mul(10)
+                .and_then(|m| m.checked_add(c.to_digit(10).unwrap() as i128))
+                .ok_or_else(err)?;
+        }
+        Ok(Decimal::new(if neg { -mantissa } else { mantissa }, scale))
+    }
+
+    //change scale, digits dropped when lowering the scale are rounded by mode
+    pub fn rescale(&self, scale: u8, mode: RoundingMode) -> Result<Decimal> {
+        let overflow = || decimal_err(format!("decimal {} overflows at scale {}", self, scale));
+        if scale >= self.scale {
+            let factor = pow10(scale - self.scale).ok_or_else(overflow)?;
+            let mantissa = self.mantissa.checked_mul(factor).ok_or_else(overflow)?;
+            return Ok(Decimal::new(mantissa, scale));
+        }
+
+        //a factor beyond i128 means every digit is dropped
+        let mantissa = match pow10(self.scale - scale) {
+            Some(factor) => round_div(self.mantissa, factor, mode),
+            None => round_div(self.mantissa.signum(), i128::MAX, mode),
+        };
+        Ok(Decimal::new(mantissa, scale))
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        //go via text, so 0.1 is the nearest f64 of 0.1 and not 1 * 0.1f64 artefacts
+        self.to_string().parse::<f64>().unwrap()
+    }
+
+    //shortest text of x that round trips is read exactly, then rounded to scale
+    pub fn from_f64(x: f64, scale: u8, mode: RoundingMode) -> Result<Decimal> {
+        if !x.is_finite() {
+            return Err(decimal_err(format!("cannot represent {} as decimal", x)));
+        }
+        Decimal::parse(&x.to_string())?.rescale(scale, mode)
+    }
+
+    pub fn fits_precision(&self, precision: u8) -> bool {
+        n_digits(self.mantissa) <= precision as u32
+    }
+}
+
+//by value, equal values of different scale order by scale so the order agrees with ==
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Decimal) -> Option<Ordering> {
+        if self.scale == other.scale {
+            return Some(self.mantissa.cmp(&other.mantissa));
+        }
+        let scale = self.scale.max(other.scale);
+        let by_value = match (
+            self.rescale(scale, RoundingMode::Down),
+            other.rescale(scale, RoundingMode::Down),
+        ) {
+            (Ok(a), Ok(b)) => a.mantissa.cmp(&b.mantissa),
+            _ => self.to_f64().partial_cmp(&other.to_f64())?,
+        };
+        Some(by_value.then(self.scale.cmp(&other.scale)))
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits = self.mantissa.unsigned_abs().to_string();
+        let sign = if self.mantissa < 0 { "-" } else { "" };
+        let scale = self.scale as usize;
+        if scale == 0 {
+            return write!(f, "{}{}", sign, digits);
+        }
+        let padded = format!("{:0>width$}", digits, width = scale + 1);
+        let (int_part, frac_part) = padded.split_at(padded.len() - scale);
+        write!(f, "{}{}.{}", sign, int_part, frac_part)
+    }
+}
+
+//no arguments for the default of whole numbers of up to 38 digits
+fn decimal_args(precision: u8, scale: u8) -> String {
+    if (precision, scale) == (MAX_PRECISION, 0) {
+        String::new()
+    } else {
+        format!("{},{}", precision, scale)
+    }
+}
+
+//parse the [precision,scale] arguments of the schema, e.g. decimal[18,4]
+pub fn parse_decimal_args(args: &str) -> Result<(u8, u8)> {
+    let err = || {
+        Box::new(ColError {
+            errorcode: ColErrorcode::SchemaSyntax,
+            error_msg: format!(
+                "decimal takes [precision,scale] with scale <= precision <= {}, got \"{}\"",
+                MAX_PRECISION, args
+            ),
+        })
+    };
+    let (p, s) = args.split_once(',').ok_or_else(err)?;
+    let precision = p.trim().parse::<u8>().map_err(|_| err())?;
+    let scale = s.trim().parse::<u8>().map_err(|_| err())?;
+    if precision == 0 || precision > MAX_PRECISION || scale > precision {
+        return Err(err());
+    }
+    Ok((precision, scale))
+}
+
+//exact fixed-point numbers, values are mantissas at the column scale
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColDecimal {
+    pub data: Vec<i128>,
+    pub precision: u8,
+    pub scale: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColDecimalNullable {
+    pub data: Vec<i128>,
+    pub validity: Bitmap,
+    pub precision: u8,
+    pub scale: u8,
+}
+
+//parse text to a mantissa at scale, text with more non-zero fraction digits is not exact
+fn parse_at(x: &str, precision: u8, scale: u8) -> Result<i128> {
+    let d = Decimal::parse(x)?;
+    let rescaled = d.rescale(scale, RoundingMode::Down)?;
+    if rescaled.rescale(d.scale, RoundingMode::Down)? != d {
+        return Err(decimal_err(format!(
+            "\"{}\" has more than {} fraction digits",
+            x.trim(),
+            scale
+        )));
+    }
+    check_precision(rescaled, precision)?;
+    Ok(rescaled.mantissa)
+}
+
+fn check_precision(d: Decimal, precision: u8) -> Result<()> {
+    if !d.fits_precision(precision) {
+        return Err(decimal_err(format!(
+            "{} exceeds precision {}",
+            d, precision
+        )));
+    }
+    Ok(())
+}
+
+macro_rules! decimal_aggregations {
+    ($col:ident, $iter:ident) => {
+        impl $col {
+            //exact sum at the column scale, errors on i128 overflow
+            pub fn sum(&self) -> Result<Decimal> {
+                let mut total: i128 = 0;
+                for x in self.$iter() {
+                    total = total
+                        .checked_add(x)
+                        .ok_or_else(|| decimal_err(String::from("decimal sum overflows")))?;
+                }
+                Ok(Decimal::new(total, self.scale))
+            }
+
+            //mean rounded to the column scale by mode
+            pub fn mean(&self, mode: RoundingMode) -> Result<Option<Decimal>> {
+                let n = self.$iter().count() as i128;
+                if n == 0 {
+                    return Ok(None);
+                }
+                let sum = self.sum()?.mantissa;
+                Ok(Some(Decimal::new(round_div(sum, n, mode), self.scale)))
+            }
+
+            pub fn min(&self) -> Option<Decimal> {
+                self.$iter().min().map(|x| Decimal::new(x, self.scale))
+            }
+
+            pub fn max(&self) -> Option<Decimal> {
+                self.$iter().max().map(|x| Decimal::new(x, self.scale))
+            }
+        }
+    };
+}
+
+impl ColDecimal {
+    pub fn new(precision: u8, scale: u8) -> ColDecimal {
+        ColDecimal {
+            data: Vec::new(),
+            precision,
+            scale,
+        }
+    }
+
+    fn values(&self) -> impl Iterator<Item = i128> + '_ {
+        self.data.iter().copied()
+    }
+
+    pub fn get(&self, idx: usize) -> Decimal {
+        Decimal::new(self.data[idx], self.scale)
+    }
+
+    pub fn to_f64(&self) -> Vec<f64> {
+        (0..self.data.len()).map(|i| self.get(i).to_f64()).collect()
+    }
+
+    pub fn from_f64(
+        values: &[f64],
+        precision: u8,
+        scale: u8,
+        mode: RoundingMode,
+    ) -> Result<ColDecimal> {
+        let mut col = ColDecimal::new(precision, scale);
+        for x in values.iter() {
+            let d = Decimal::from_f64(*x, scale, mode)?;
+            check_precision(d, precision)?;
+            col.data.push(d.mantissa);
+        }
+        Ok(col)
+    }
+}
+
+impl ColDecimalNullable {
+    pub fn new(precision: u8, scale: u8) -> ColDecimalNullable {
+        ColDecimalNullable {
+            data: Vec::new(),
+            validity: Bitmap::new(),
+            precision,
+            scale,
+        }
+    }
+
+    //valid mantissas only
+    fn values(&self) -> impl Iterator<Item = i128> + '_ {
+        self.data
+            .iter()
+            .zip(self.validity.iter())
+            .filter_map(|(x, valid)| valid.then_some(*x))
+    }
+
+    pub fn push(&mut self, mantissa: Option<i128>) {
+        self.data.push(mantissa.unwrap_or_default());
+        self.validity.push(mantissa.is_some());
+    }
+
+    pub fn get(&self, idx: usize) -> Option<Decimal> {
+        self.validity
+            .get(idx)
+            .then(|| Decimal::new(self.data[idx], self.scale))
+    }
+
+    pub fn to_f64(&self) -> Vec<Option<f64>> {
+        (0..self.data.len())
+            .map(|i| self.get(i).map(|d| d.to_f64()))
+            .collect()
+    }
+
+    pub fn from_f64(
+        values: &[Option<f64>],
+        precision: u8,
+        scale: u8,
+        mode: RoundingMode,
+    ) -> Result<ColDecimalNullable> {
+        let mut col = ColDecimalNullable::new(precision, scale);
+        for x in values.iter() {
+            let value = match x {
+                Some(y) => {
+                    let d = Decimal::from_f64(*y, scale, mode)?;
+                    check_precision(d, precision)?;
+                    Some(d.mantissa)
+                }
+                None => None,
+            };
+            col.push(value);
+        }
+        Ok(col)
+    }
+}
+
+decimal_aggregations!(ColDecimal, values);
+decimal_aggregations!(ColDecimalNullable, values);
+
+#[typetag::serde]
+impl VectorData for ColDecimal {
+    fn push_from_str(&mut self, x: &str) -> Result<()> {
+        self.data.push(parse_at(x, self.precision, self.scale)?);
+        Ok(())
+    }
+    fn to_string(&self) -> String {
+        (0..self.data.len())
+            .map(|i| self.get(i).to_string())
+            .fold(String::new(), |a, b| a + &b[..] + ", ")
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+    }
+    fn truncate(&mut self, len: usize) {
+        self.data.truncate(len);
+    }
+    fn boxed_clone(&self) -> Box<dyn VectorData> {
+        Box::new(self.clone())
+    }
+    fn dtype(&self) -> Dtype {
+        Dtype::ColDecimal(self.precision, self.scale)
+    }
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+    fn get_value(&self, idx: usize) -> Value<'_> {
+        Value::Decimal(self.get(idx))
+    }
+    fn take(&self, indices: &[usize]) -> Box<dyn VectorData> {
+        Box::new(ColDecimal {
+            data: indices.iter().map(|i| self.data[*i]).collect(),
+            precision: self.precision,
+            scale: self.scale,
+        })
+    }
+    fn type_args(&self) -> String {
+        decimal_args(self.precision, self.scale)
+    }
+    fn extend(&mut self, other: &dyn VectorData) -> Result<()> {
+        let other: &Self = extend_source(other, self.dtype())?;
+        self.data.extend_from_slice(&other.data);
+        Ok(())
+    }
+}
+
+#[typetag::serde]
+impl VectorData for ColDecimalNullable {
+    fn push_from_str(&mut self, x: &str) -> Result<()> {
+        //empty cells are null, malformed numbers are still an error
+        let value = if x.trim().is_empty() {
+            None
+        } else {
+            Some(parse_at(x, self.precision, self.scale)?)
+        };
+        self.push(value);
+        Ok(())
+    }
+    fn to_string(&self) -> String {
+        (0..self.data.len())
+            .map(|i| match self.get(i) {
+                Some(d) => d.to_string(),
+                None => String::from("NA"),
+            })
+            .fold(String::new(), |a, b| a + &b[..] + ", ")
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+        self.validity.reserve(additional);
+    }
+    fn truncate(&mut self, len: usize) {
+        self.data.truncate(len);
+        self.validity.truncate(len);
+    }
+    fn boxed_clone(&self) -> Box<dyn VectorData> {
+        Box::new(self.clone())
+    }
+    fn dtype(&self) -> Dtype {
+        Dtype::ColDecimalNullable(self.precision, self.scale)
+    }
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+    fn get_value(&self, idx: usize) -> Value<'_> {
+        self.get(idx).map_or(Value::Null, Value::Decimal)
+    }
+    fn take(&self, indices: &[usize]) -> Box<dyn VectorData> {
+        Box::new(ColDecimalNullable {
+            data: indices.iter().map(|i| self.data[*i]).collect(),
+            validity: self.validity.take(indices),
+            precision: self.precision,
+            scale: self.scale,
+        })
+    }
+    fn validity(&self) -> Option<&Bitmap> {
+        Some(&self.validity)
+    }
+    fn type_args(&self) -> String {
+        decimal_args(self.precision, self.scale)
+    }
+    fn extend(&mut self, other: &dyn VectorData) -> Result<()> {
+        let other: &Self = extend_source(other, self.dtype())?;
+        self.data.extend_from_slice(&other.data);
+        self.validity.extend(&other.validity);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_display() {
+        let d = Decimal::parse("-12.3400").unwrap();
+        assert_eq!(d, Decimal::new(-123400, 4));
+        assert_eq!(d.to_string(), "-12.3400");
+        assert_eq!(Decimal::new(5, 3).to_string(), "0.005");
+        assert_eq!(Decimal::parse(".5").unwrap(), Decimal::new(5, 1));
+        assert!(Decimal::parse("1e3").is_err());
+        assert!(Decimal::parse("-").is_err());
+    }
+
+    #[test]
+    fn rounding_modes() {
+        let d = Decimal::parse("-2.345").unwrap();
+        let r = |mode| d.rescale(2, mode).unwrap().to_string();
+        assert_eq!(r(RoundingMode::HalfEven), "-2.34");
+        assert_eq!(r(RoundingMode::HalfUp), "-2.35");
+        assert_eq!(r(RoundingMode::Down), "-2.34");
+        assert_eq!(r(RoundingMode::Up), "-2.35");
+        assert_eq!(r(RoundingMode::Floor), "-2.35");
+        assert_eq!(r(RoundingMode::Ceiling), "-2.34");
+
+        let d = Decimal::from_f64(0.125, 2, RoundingMode::HalfEven).unwrap();
+        assert_eq!(d.to_string(), "0.12");
+        assert_eq!(Decimal::new(10, 2).to_f64(), 0.1);
+    }
+
+    #[test]
+    fn column_exact_parse_and_sum() {
+        let mut col = ColDecimal::new(6, 2);
+        for x in ["0.10", "0.2", "1234.00", "-0.3"] {
+            col.push_from_str(x).unwrap();
+        }
+        assert_eq!(col.sum().unwrap().to_string(), "1234.00");
+        assert_eq!(col.min().unwrap().to_string(), "-0.30");
+        let mean = col.mean(RoundingMode::HalfEven).unwrap().unwrap();
+        assert_eq!(mean.to_string(), "308.50");
+
+        //digits beyond the scale or precision are rejected, not rounded
+        assert!(col.push_from_str("0.125").is_err());
+        assert!(col.push_from_str("12345.00").is_err());
+        assert!(col.push_from_str("0.1200").is_ok());
+
+        let floats = ColDecimal::from_f64(&[0.1, 2.675], 6, 2, RoundingMode::HalfUp).unwrap();
+        assert_eq!(VectorData::to_string(&floats), "0.10, 2.68, ");
+        assert_eq!(floats.to_f64(), vec![0.1, 2.68]);
+    }
+
+    #[test]
+    fn decimal_args() {
+        assert_eq!(parse_decimal_args("18, 4").unwrap(), (18, 4));
+        assert!(parse_decimal_args("4,18").is_err());
+        assert!(parse_decimal_args("39,2").is_err());
+        assert!(parse_decimal_args("18").is_err());
+    }
+}
@@ -0,0 +1,124 @@
+use super::column::{ColI64, Column, VectorData};
+use super::groupby::group_rows;
+use super::{DataFrame, Result};
+
+//which row of a set of duplicates drop_duplicates keeps, None drops them all
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Keep {
+    #[default]
+    First,
+    Last,
+    None,
+}
+
+fn kept_rows(groups: &[Vec<usize>], keep: Keep) -> Vec<usize> {
+    let mut rows: Vec<usize> = groups
+        .iter()
+        .filter_map(|g| match keep {
+            Keep::First => Some(g[0]),
+            Keep::Last => Some(g[g.len() - 1]),
+            Keep::None if g.len() == 1 => Some(g[0]),
+            Keep::None => None,
+        })
+        .collect();
+    rows.sort_unstable();
+    rows
+}
+
+//distinct rows of cols with a column count of their number of rows, most frequent first
+fn counts_of(cols: Vec<&Column>, nrow: usize) -> Result<DataFrame> {
+    let data: Vec<&dyn VectorData> = cols.iter().map(|c| c.data.as_ref()).collect();
+    let mut groups = group_rows(&data, nrow);
+    groups.sort_by_key(|g| std::cmp::Reverse(g.len()));
+    let first: Vec<usize> = groups.iter().map(|g| g[0]).collect();
+    let mut out: Vec<Column> = cols.iter().map(|c| c.take(&first)).collect();
+    let counts = groups.iter().map(|g| g.len() as i64).collect();
+    out.push(Column::from_data(
+        Some(String::from("count")),
+        Box::new(ColI64 { data: counts }),
+    ));
+    DataFrame::from_columns(out)
+}
+
+impl Column {
+    //distinct cells in order of their first row, null is one of them
+    pub fn unique(&self) -> Column {
+        let groups = group_rows(&[self.data.as_ref()], self.data.len());
+        self.take(&kept_rows(&groups, Keep::First))
+    }
+
+    pub fn value_counts(&self) -> Result<DataFrame> {
+        counts_of(vec![self], self.data.len())
+    }
+}
+
+impl DataFrame {
+    //rows whose cells in subset equal those of another row are duplicates, an empty subset
+    //compares all columns. rows keep their order, nulls equal nulls
+    pub fn drop_duplicates(&self, subset: &[&str], keep: Keep) -> Result<DataFrame> {
+        let cols = self.subset(subset)?;
+        let data: Vec<&dyn VectorData> = cols.iter().map(|c| c.data.as_ref()).collect();
+        let groups = group_rows(&data, self.shape().0);
+        self.take(&kept_rows(&groups, keep))
+    }
+
+    //distinct rows, keeping the first of each
+    pub fn unique(&self) -> Result<DataFrame> {
+        self.drop_duplicates(&[], Keep::First)
+    }
+
+    //distinct rows of the subset columns with their count, most frequent first
+    pub fn value_counts(&self, subset: &[&str]) -> Result<DataFrame> {
+        counts_of(self.subset(subset)?, self.shape().0)
+    }
+
+    fn subset(&self, names: &[&str]) -> Result<Vec<&Column>> {
+        if names.is_empty() {
+            return Ok(self.data.iter().collect());
+        }
+        names.iter().map(|name| self.column(name)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataframe::csv_read_str_iter;
+
+    #[test]
+    fn duplicates_and_counts() {
+        let mycsvstr = "a,1,x\nb,,y\na,1,z\nb,,y\nc,0,x\na,-0,x";
+        let myschema = "k:stringPool,n:doubleNullable,s:stringNullable";
+        let df = csv_read_str_iter(mycsvstr, myschema).unwrap();
+
+        let first = df.drop_duplicates(&["k", "n"], Keep::First).unwrap();
+        assert_eq!(first.column("s").unwrap().data.to_string(), "x, y, x, x, ");
+        let last = df.drop_duplicates(&["k", "n"], Keep::Last).unwrap();
+        assert_eq!(last.column("s").unwrap().data.to_string(), "z, y, x, x, ");
+        let none = df.drop_duplicates(&["k", "n"], Keep::None).unwrap();
+        assert_eq!(none.column("s").unwrap().data.to_string(), "x, x, ");
+        assert_eq!(df.unique().unwrap().shape(), (5, 3));
+        assert!(df.drop_duplicates(&["nope"], Keep::First).is_err());
+
+        let counts = df.value_counts(&["k"]).unwrap();
+        assert_eq!(counts.names(), vec![Some("k"), Some("count")]);
+        assert_eq!(
+            counts.column("k").unwrap().data.to_string(),
+            "[ \"a\", \"b\", \"c\",]"
+        );
+        assert_eq!(
+            counts.column("count").unwrap().as_i64().unwrap(),
+            &[3, 2, 1]
+        );
+
+        //-0 and 0 are one value, null is a value of its own
+        let n = df.column("n").unwrap();
+        assert_eq!(n.unique().data.to_string(), "1, NA, 0, ");
+        let counts = n.value_counts().unwrap();
+        assert_eq!(counts.column("n").unwrap().data.to_string(), "1, NA, 0, ");
+        assert_eq!(
+            counts.column("count").unwrap().as_i64().unwrap(),
+            &[2, 2, 2]
+        );
+    }
+}
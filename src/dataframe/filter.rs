@@ -0,0 +1,154 @@
+use super::column::{ColBool, ColBoolNullable, Value, VectorData};
+use super::{DFError, DataFrame, Result};
+
+fn filter_err(error_msg: String) -> Box<dyn std::error::Error> {
+    Box::new(DFError {
+        error_msg,
+        sub_errors: Vec::new(),
+    })
+}
+
+//Row is a view of one row of a data frame, handed to the predicate of filter_by
+pub struct Row<'a> {
+    df: &'a DataFrame,
+    idx: usize,
+}
+
+impl<'a> Row<'a> {
+    //position of the row in the data frame
+    pub fn index(&self) -> usize {
+        self.idx
+    }
+
+    //cell of the column by name, e.g. row.get("age")? > Value::Int(30)
+    pub fn get(&self, name: &str) -> Result<Value<'a>> {
+        Ok(self.df.column(name)?.data.get_value(self.idx))
+    }
+
+    //cell of the column No. i
+    pub fn value(&self, i: usize) -> Value<'a> {
+        self.df.data[i].data.get_value(self.idx)
+    }
+
+    pub fn is_null(&self, name: &str) -> Result<bool> {
+        Ok(self.df.column(name)?.data.is_null(self.idx))
+    }
+}
+
+//rows selected by a bool or boolNullable mask, null counts as false
+fn mask_indices(mask: &dyn VectorData) -> Result<Vec<usize>> {
+    let any = mask.as_any();
+    if let Some(m) = any.downcast_ref::<ColBool>() {
+        Ok(m.true_indices())
+    } else if let Some(m) = any.downcast_ref::<ColBoolNullable>() {
+        Ok(m.true_indices())
+    } else {
+        Err(filter_err(format!(
+            "a mask is bool or boolNullable, not {}",
+            mask.dtype()
+        )))
+    }
+}
+
+impl DataFrame {
+    //the rows at indices in order, rows may repeat
+    pub fn take(&self, indices: &[usize]) -> Result<DataFrame> {
+        let (nrow, _) = self.shape();
+        if let Some(i) = indices.iter().find(|i| **i >= nrow) {
+            return Err(filter_err(format!(
+                "row {} is out of bounds for {} rows",
+                i, nrow
+            )));
+        }
+        DataFrame::from_columns(self.data.iter().map(|c| c.take(indices)).collect())
+    }
+
+    //rows where the mask is true, e.g. df.filter(df.column("active")?.data.as_ref())
+    pub fn filter(&self, mask: &dyn VectorData) -> Result<DataFrame> {
+        let (nrow, _) = self.shape();
+        if mask.len() != nrow {
+            return Err(filter_err(format!(
+                "mask has {} rows, the data frame {}",
+                mask.len(),
+                nrow
+            )));
+        }
+        self.take(&mask_indices(mask)?)
+    }
+
+    //rows the predicate accepts, the first error of the predicate is returned
+    pub fn filter_by<F>(&self, mut predicate: F) -> Result<DataFrame>
+    where
+        F: FnMut(&Row) -> Result<bool>,
+    {
+        let mut indices = Vec::new();
+        for idx in 0..self.shape().0 {
+            if predicate(&Row { df: self, idx })? {
+                indices.push(idx);
+            }
+        }
+        self.take(&indices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitmap::Bitmap;
+    use crate::dataframe::column::{ColStringPool, Dtype};
+    use crate::dataframe::csv_read_str_iter;
+
+    #[test]
+    fn filter_rows() {
+        let mycsvstr = "1,true,de,34\n2,false,fr,\n3,,de,61\n4,true,it,17";
+        let myschema = "id:int,active:boolNullable,country:stringPool,age:intNullable";
+        let df = csv_read_str_iter(mycsvstr, myschema).unwrap();
+
+        let active = df
+            .filter(df.column("active").unwrap().data.as_ref())
+            .unwrap();
+        assert_eq!(active.column("id").unwrap().as_i32().unwrap(), &[1, 4]);
+        assert_eq!(
+            active.column("country").unwrap().data.to_string(),
+            "[ \"de\", \"it\",]"
+        );
+        assert_eq!(active.column("age").unwrap().dtype(), Dtype::ColIntNullable);
+
+        let mask = ColBool::from_bools(&[true, false]);
+        assert!(df.filter(&mask).is_err());
+        assert!(df.filter(df.column("id").unwrap().data.as_ref()).is_err());
+
+        let older = df
+            .filter_by(|row| Ok(row.get("age")? > Value::Int(30)))
+            .unwrap();
+        assert_eq!(older.column("id").unwrap().as_i32().unwrap(), &[1, 3]);
+        let german = df
+            .filter_by(|row| {
+                let de = row.get("country")? == Value::Str("de");
+                Ok(de && !row.is_null("active")?)
+            })
+            .unwrap();
+        assert_eq!(german.shape(), (1, 4));
+        assert!(df
+            .filter_by(|row| Ok(row.get("nope")? == Value::Null))
+            .is_err());
+
+        let taken = df.take(&[3, 0, 3]).unwrap();
+        assert_eq!(taken.column("id").unwrap().as_i32().unwrap(), &[4, 1, 4]);
+        assert!(df.take(&[4]).is_err());
+    }
+
+    #[test]
+    fn filter_compacts_string_pool() {
+        let mut col = ColStringPool::default();
+        for i in 0..2000 {
+            col.push_from_str(&format!("string number {}", i)).unwrap();
+        }
+        let mask: Bitmap = (0..2000).map(|i| i % 1000 == 0).collect();
+        let out = col.filter(&mask);
+        let pool = &out.as_any().downcast_ref::<ColStringPool>().unwrap().data;
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool.get_str(1), "string number 1000");
+        assert_eq!(pool.get_last_idx(), 0);
+    }
+}
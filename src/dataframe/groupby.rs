@@ -0,0 +1,531 @@
+use super::column::{
+    ColCategorical, ColDecimal, ColDecimalNullable, ColF64, ColF64Nullable, ColI64, ColI64Nullable,
+    ColU64, ColU64Nullable, Column, Dtype, RoundingMode, Value, VectorData,
+};
+use super::concat::numeric_kind;
+use super::decimal::{round_div, Decimal, MAX_PRECISION};
+use super::sort::total_cmp;
+use super::{DFError, DataFrame, Result};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+//Agg is an aggregation of the rows of a group, nulls are skipped except by first and last
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Agg {
+    Count, //non-null cells
+    Sum,
+    Mean,
+    Min,
+    Max,
+    First,
+    Last,
+    NUnique, //distinct non-null cells
+    Std,     //sample standard deviation
+    Var,     //sample variance
+    Median,
+}
+
+impl fmt::Display for Agg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Agg::Count => "count",
+            Agg::Sum => "sum",
+            Agg::Mean => "mean",
+            Agg::Min => "min",
+            Agg::Max => "max",
+            Agg::First => "first",
+            Agg::Last => "last",
+            Agg::NUnique => "n_unique",
+            Agg::Std => "std",
+            Agg::Var => "var",
+            Agg::Median => "median",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+fn group_err(error_msg: String) -> Box<dyn std::error::Error> {
+    Box::new(DFError {
+        error_msg,
+        sub_errors: Vec::new(),
+    })
+}
+
+//KeyPart is the hashable form of a cell, floats hash by their bits with a single NaN
+//and a single zero
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum KeyPart<'a> {
+    Null,
+    Int(i64),
+    UInt(u64),
+    Float(u64),
+    Bool(bool),
+    Str(&'a str),
+    Bytes(&'a [u8]),
+    Text(String), //decimals, lists and structs by their text
+}
+
+pub(crate) fn key_part(value: Value<'_>) -> KeyPart<'_> {
+    match value {
+        Value::Null => KeyPart::Null,
+        Value::Int(x) | Value::Datetime(x, _) | Value::Duration(x, _) => KeyPart::Int(x),
+        Value::Date(x) => KeyPart::Int(x as i64),
+        Value::UInt(x) => KeyPart::UInt(x),
+        Value::Double(x) if x.is_nan() => KeyPart::Float(f64::NAN.to_bits()),
+        Value::Double(x) => KeyPart::Float((x + 0.0).to_bits()),
+        Value::Bool(x) => KeyPart::Bool(x),
+        Value::Str(x) => KeyPart::Str(x),
+        Value::Binary(x) => KeyPart::Bytes(x),
+        value => KeyPart::Text(value.to_string()),
+    }
+}
+
+pub(crate) fn row_key<'a>(cols: &[&'a dyn VectorData], idx: usize) -> Vec<KeyPart<'a>> {
+    cols.iter().map(|c| key_part(c.get_value(idx))).collect()
+}
+
+//row indices of each distinct key, groups in order of their first row
+pub(crate) fn group_rows(cols: &[&dyn VectorData], nrow: usize) -> Vec<Vec<usize>> {
+    //a single categorical key groups by code instead of hashing the strings
+    if let [col] = cols {
+        if let Some(cat) = col.as_any().downcast_ref::<ColCategorical>() {
+            let mut groups = cat.group_indices();
+            groups.retain(|g| !g.is_empty());
+            groups.sort_by_key(|g| g[0]);
+            return groups;
+        }
+    }
+    let mut positions: HashMap<Vec<KeyPart>, usize> = HashMap::new();
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for idx in 0..nrow {
+        let g = *positions.entry(row_key(cols, idx)).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        groups[g].push(idx);
+    }
+    groups
+}
+
+//GroupBy holds the rows of each key of a data frame, see DataFrame::group_by
+pub struct GroupBy<'a> {
+    df: &'a DataFrame,
+    keys: Vec<&'a Column>,
+    groups: Vec<Vec<usize>>,
+}
+
+impl GroupBy<'_> {
+    //row indices of each group, in order of the first row of the group
+    pub fn groups(&self) -> &[Vec<usize>] {
+        &self.groups
+    }
+
+    //one row per group with the key columns and a column named like x_sum per aggregation
+    pub fn agg(&self, aggs: &[(&str, Agg)]) -> Result<DataFrame> {
+        let first: Vec<usize> = self.groups.iter().map(|g| g[0]).collect();
+        let mut data: Vec<Column> = self.keys.iter().map(|c| c.take(&first)).collect();
+        for (name, agg) in aggs.iter() {
+            let mut out = aggregate(self.df.column(name)?, &self.groups, *agg)?;
+            out.name = Some(format!("{}_{}", name, agg));
+            data.push(out);
+        }
+        DataFrame::from_columns(data)
+    }
+}
+
+//non-null cells of the group as f64, for the statistics
+fn numbers(data: &dyn VectorData, group: &[usize]) -> Vec<f64> {
+    group
+        .iter()
+        .filter_map(|&i| match data.get_value(i) {
+            Value::Int(x) => Some(x as f64),
+            Value::UInt(x) => Some(x as f64),
+            Value::Double(x) => Some(x),
+            Value::Decimal(x) => Some(x.to_f64()),
+            _ => None,
+        })
+        .collect()
+}
+
+//mantissas of the non-null cells of the group, all at the scale of the column
+fn mantissas(data: &dyn VectorData, group: &[usize]) -> Vec<i128> {
+    group
+        .iter()
+        .filter_map(|&i| match data.get_value(i) {
+            Value::Decimal(x) => Some(x.mantissa),
+            _ => None,
+        })
+        .collect()
+}
+
+//sum, mean and median of decimals at the column scale, sums are exact and means and
+//medians round half to even
+fn decimal_aggregate(
+    data: &dyn VectorData,
+    groups: &[Vec<usize>],
+    agg: Agg,
+    scale: u8,
+) -> Result<Column> {
+    //sums beyond 38 digits do not fit a decimal column
+    let sum = |x: &[i128]| {
+        x.iter()
+            .try_fold(0i128, |acc, v| acc.checked_add(*v))
+            .filter(|s| Decimal::new(*s, scale).fits_precision(MAX_PRECISION))
+            .ok_or_else(|| group_err(String::from("decimal sum overflows")))
+    };
+    //sums of nullable columns are null for groups without values
+    if agg == Agg::Sum && data.dtype().is_nullable() {
+        let mut out = ColDecimalNullable::new(MAX_PRECISION, scale);
+        for g in groups.iter() {
+            let x = mantissas(data, g);
+            out.push(if x.is_empty() { None } else { Some(sum(&x)?) });
+        }
+        return Ok(Column::from_data(None, Box::new(out)));
+    }
+    if agg == Agg::Sum {
+        let mut out = ColDecimal::new(MAX_PRECISION, scale);
+        for g in groups.iter() {
+            out.data.push(sum(&mantissas(data, g))?);
+        }
+        return Ok(Column::from_data(None, Box::new(out)));
+    }
+    let mut out = ColDecimalNullable::new(MAX_PRECISION, scale);
+    for g in groups.iter() {
+        let mut x = mantissas(data, g);
+        x.sort_unstable();
+        let n = x.len();
+        out.push(match agg {
+            _ if n == 0 => None,
+            Agg::Mean => Some(round_div(sum(&x)?, n as i128, RoundingMode::HalfEven)),
+            _ if n % 2 == 1 => Some(x[n / 2]),
+            _ => Some(round_div(
+                sum(&x[n / 2 - 1..=n / 2])?,
+                2,
+                RoundingMode::HalfEven,
+            )),
+        });
+    }
+    Ok(Column::from_data(None, Box::new(out)))
+}
+
+fn mean(x: &[f64]) -> Option<f64> {
+    match x.len() {
+        0 => None,
+        n => Some(x.iter().sum::<f64>() / n as f64),
+    }
+}
+
+fn var(x: &[f64]) -> Option<f64> {
+    let m = mean(x)?;
+    match x.len() {
+        0 | 1 => None,
+        n => Some(x.iter().map(|v| (v - m) * (v - m)).sum::<f64>() / (n - 1) as f64),
+    }
+}
+
+fn median(mut x: Vec<f64>) -> Option<f64> {
+    x.sort_by(|a, b| total_cmp(&Value::Double(*a), &Value::Double(*b)));
+    let n = x.len();
+    match n {
+        0 => None,
+        _ if n % 2 == 1 => Some(x[n / 2]),
+        _ => Some((x[n / 2 - 1] + x[n / 2]) / 2.0),
+    }
+}
+
+//the non-null cells of each group folded by add, None for groups without values
+fn fold_groups<T: Default>(
+    data: &dyn VectorData,
+    groups: &[Vec<usize>],
+    add: impl Fn(T, Value) -> Result<T>,
+) -> Result<Vec<Option<T>>> {
+    let mut out = Vec::with_capacity(groups.len());
+    for g in groups.iter() {
+        let mut acc = None;
+        for i in g.iter().filter(|&&i| !data.is_null(i)) {
+            acc = Some(add(acc.unwrap_or_default(), data.get_value(*i))?);
+        }
+        out.push(acc);
+    }
+    Ok(out)
+}
+
+//the nullable column of the sums if nullable, else the column with 0 for empty groups
+macro_rules! sum_column {
+    ($sums:expr, $nullable:expr, $out:ident, $out_nullable:ident) => {{
+        let out: Box<dyn VectorData> = if $nullable {
+            let mut out = $out_nullable::default();
+            $sums.into_iter().for_each(|x| out.push(x));
+            Box::new(out)
+        } else {
+            let data = $sums.into_iter().map(Option::unwrap_or_default).collect();
+            Box::new($out { data })
+        };
+        out
+    }};
+}
+
+//sums are i64 for signed ints, u64 for unsigned ints and f64 for floats. sums of nullable
+//columns are of the nullable dtype and null for groups without values
+fn sum(data: &dyn VectorData, groups: &[Vec<usize>], kind: char) -> Result<Box<dyn VectorData>> {
+    let overflow = || group_err(String::from("sum overflows"));
+    let nullable = data.dtype().is_nullable();
+    let out = match kind {
+        'i' => {
+            let sums = fold_groups(data, groups, |acc: i64, v| match v {
+                Value::Int(x) => acc.checked_add(x).ok_or_else(overflow),
+                _ => Ok(acc),
+            })?;
+            sum_column!(sums, nullable, ColI64, ColI64Nullable)
+        }
+        'u' => {
+            let sums = fold_groups(data, groups, |acc: u64, v| match v {
+                Value::Int(x) => acc.checked_add(x as u64).ok_or_else(overflow),
+                Value::UInt(x) => acc.checked_add(x).ok_or_else(overflow),
+                _ => Ok(acc),
+            })?;
+            sum_column!(sums, nullable, ColU64, ColU64Nullable)
+        }
+        _ => {
+            let sums = fold_groups(data, groups, |acc: f64, v| match v {
+                Value::Double(x) => Ok(acc + x),
+                _ => Ok(acc),
+            })?;
+            sum_column!(sums, nullable, ColF64, ColF64Nullable)
+        }
+    };
+    Ok(out)
+}
+
+fn aggregate(col: &Column, groups: &[Vec<usize>], agg: Agg) -> Result<Column> {
+    let data = col.data.as_ref();
+    let non_null =
+        |g: &[usize]| -> Vec<usize> { g.iter().copied().filter(|&i| !data.is_null(i)).collect() };
+    let pick = |choose: &dyn Fn(&[usize]) -> usize| {
+        let indices: Vec<usize> = groups.iter().map(|g| choose(g)).collect();
+        Ok(col.take(&indices))
+    };
+    let counts = |count: &dyn Fn(&[usize]) -> usize| {
+        let data = groups.iter().map(|g| count(g) as i64).collect();
+        Ok(Column::from_data(None, Box::new(ColI64 { data })))
+    };
+
+    match agg {
+        Agg::Count => return counts(&|g| non_null(g).len()),
+        Agg::NUnique => {
+            return counts(&|g| {
+                let distinct: HashSet<KeyPart> = non_null(g)
+                    .into_iter()
+                    .map(|i| key_part(data.get_value(i)))
+                    .collect();
+                distinct.len()
+            })
+        }
+        Agg::First => return pick(&|g| g[0]),
+        Agg::Last => return pick(&|g| g[g.len() - 1]),
+        //groups without values pick a null row
+        Agg::Min | Agg::Max => {
+            return pick(&|g| {
+                let cmp =
+                    |i: &usize, j: &usize| total_cmp(&data.get_value(*i), &data.get_value(*j));
+                let found = match agg {
+                    Agg::Min => non_null(g).into_iter().min_by(cmp),
+                    _ => non_null(g).into_iter().max_by(cmp),
+                };
+                found.unwrap_or(g[0])
+            })
+        }
+        _ => (),
+    }
+
+    if let Dtype::ColDecimal(_, scale) | Dtype::ColDecimalNullable(_, scale) = col.dtype() {
+        //std and var of decimals are f64 like the others
+        if agg != Agg::Std && agg != Agg::Var {
+            return decimal_aggregate(data, groups, agg, scale);
+        }
+    } else {
+        let kind = numeric_kind(col.dtype().non_nullable()).ok_or_else(|| {
+            group_err(format!(
+                "{} of column \"{}\" needs a numeric column, not {}",
+                agg,
+                col.name.as_deref().unwrap_or("None"),
+                col.dtype()
+            ))
+        })?;
+        if agg == Agg::Sum {
+            return Ok(Column::from_data(None, sum(data, groups, kind.0)?));
+        }
+    }
+    let mut out = ColF64Nullable::default();
+    for g in groups.iter() {
+        let x = numbers(data, g);
+        out.push(match agg {
+            Agg::Mean => mean(&x),
+            Agg::Var => var(&x),
+            Agg::Std => var(&x).map(f64::sqrt),
+            _ => median(x),
+        });
+    }
+    Ok(Column::from_data(None, Box::new(out)))
+}
+
+impl DataFrame {
+    //rows grouped by the values of the key columns, e.g.
+    //df.group_by(&["k1", "k2"])?.agg(&[("x", Agg::Sum), ("x", Agg::Mean)])?
+    //nulls form a group of their own
+    pub fn group_by(&self, keys: &[&str]) -> Result<GroupBy<'_>> {
+        let keys = keys
+            .iter()
+            .map(|name| self.column(name))
+            .collect::<Result<Vec<&Column>>>()?;
+        let cols: Vec<&dyn VectorData> = keys.iter().map(|c| c.data.as_ref()).collect();
+        Ok(GroupBy {
+            df: self,
+            groups: group_rows(&cols, self.shape().0),
+            keys,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataframe::cast::CastOptions;
+    use crate::dataframe::column::Dtype;
+    use crate::dataframe::csv_read_str_iter;
+
+    #[test]
+    fn group_by_agg() {
+        let mycsvstr = "de,a,1,2.0\nfr,b,2,\nde,a,3,4.0\nde,c,,8.0\nfr,b,4,1.0\nde,a,5,0.0";
+        let myschema = "country:stringPool,shop:string,n:intNullable,x:double?";
+        let df = csv_read_str_iter(mycsvstr, myschema).unwrap();
+
+        let out = df
+            .group_by(&["country", "shop"])
+            .unwrap()
+            .agg(&[
+                ("n", Agg::Count),
+                ("n", Agg::Sum),
+                ("x", Agg::Mean),
+                ("x", Agg::Median),
+                ("x", Agg::Std),
+                ("n", Agg::Min),
+                ("n", Agg::Max),
+                ("shop", Agg::Last),
+                ("x", Agg::NUnique),
+            ])
+            .unwrap();
+        assert_eq!(out.shape(), (3, 11));
+        assert_eq!(
+            out.column("country").unwrap().data.to_string(),
+            "[ \"de\", \"fr\", \"de\",]"
+        );
+        assert_eq!(out.column("n_count").unwrap().as_i64().unwrap(), &[3, 2, 0]);
+        assert_eq!(out.column("n_sum").unwrap().dtype(), Dtype::ColI64Nullable);
+        assert_eq!(out.column("n_sum").unwrap().data.to_string(), "9, 6, NA, ");
+        assert_eq!(out.column("x_mean").unwrap().data.to_string(), "2, 1, 8, ");
+        assert_eq!(
+            out.column("x_median").unwrap().data.to_string(),
+            "2, 1, 8, "
+        );
+        assert_eq!(out.column("x_std").unwrap().data.to_string(), "2, NA, NA, ");
+        assert_eq!(out.column("n_min").unwrap().dtype(), Dtype::ColIntNullable);
+        assert_eq!(out.column("n_min").unwrap().data.to_string(), "1, 2, NA, ");
+        assert_eq!(out.column("n_max").unwrap().data.to_string(), "5, 4, NA, ");
+        assert_eq!(
+            out.column("shop_last").unwrap().data.to_string(),
+            "a, b, c, "
+        );
+        assert_eq!(
+            out.column("x_n_unique").unwrap().as_i64().unwrap(),
+            &[3, 1, 1]
+        );
+
+        assert!(df.group_by(&["nope"]).is_err());
+        let by_country = df.group_by(&["country"]).unwrap();
+        assert_eq!(by_country.groups(), &[vec![0, 2, 3, 5], vec![1, 4]]);
+        assert!(by_country.agg(&[("shop", Agg::Sum)]).is_err());
+        let out = by_country
+            .agg(&[("x", Agg::Var), ("shop", Agg::Min)])
+            .unwrap();
+        assert_eq!(
+            out.column("x_var").unwrap().data.to_string(),
+            "11.666666666666666, NA, "
+        );
+        assert_eq!(out.column("shop_min").unwrap().data.to_string(), "a, b, ");
+    }
+
+    #[test]
+    fn group_by_null_keys_and_decimals() {
+        let mycsvstr = "a,1.50,1\n,2.25,\na,,3\n,0.10,\nb,,\na,0.75,2";
+        let myschema = "k:stringNullable,d:decimalNullable[10,2],n:intNullable";
+        let df = csv_read_str_iter(mycsvstr, myschema).unwrap();
+
+        let by_k = df.group_by(&["k"]).unwrap();
+        assert_eq!(by_k.groups(), &[vec![0, 2, 5], vec![1, 3], vec![4]]);
+        //categorical keys group by code, with the same groups
+        let mut cat = df.clone();
+        cat.data[0] = df.data[0]
+            .cast(Dtype::ColCategoricalNullable, CastOptions::strict())
+            .unwrap();
+        assert_eq!(cat.group_by(&["k"]).unwrap().groups(), by_k.groups());
+        let out = by_k
+            .agg(&[
+                ("d", Agg::Count),
+                ("d", Agg::Sum),
+                ("d", Agg::Mean),
+                ("d", Agg::Median),
+                ("d", Agg::Var),
+                ("d", Agg::Std),
+                ("d", Agg::Min),
+                ("d", Agg::Max),
+                ("d", Agg::First),
+                ("d", Agg::Last),
+                ("d", Agg::NUnique),
+                ("n", Agg::Sum),
+                ("n", Agg::Mean),
+                ("n", Agg::Median),
+                ("n", Agg::First),
+            ])
+            .unwrap();
+        let col = |name: &str| out.column(name).unwrap().data.to_string();
+        assert_eq!(col("k"), "a, NA, b, ");
+        assert_eq!(out.column("d_count").unwrap().as_i64().unwrap(), &[2, 2, 0]);
+        assert_eq!(
+            out.column("d_sum").unwrap().dtype(),
+            Dtype::ColDecimalNullable(38, 2)
+        );
+        assert_eq!(col("d_sum"), "2.25, 2.35, NA, ");
+        //1.175 rounds half to even
+        assert_eq!(col("d_mean"), "1.12, 1.18, NA, ");
+        assert_eq!(col("d_median"), "1.12, 1.18, NA, ");
+        assert_eq!(col("d_var"), "0.28125, 2.31125, NA, ");
+        assert_eq!(col("d_std").split(", ").nth(2), Some("NA"));
+        assert_eq!(col("d_min"), "0.75, 0.10, NA, ");
+        assert_eq!(col("d_max"), "1.50, 2.25, NA, ");
+        assert_eq!(col("d_first"), "1.50, 2.25, NA, ");
+        assert_eq!(col("d_last"), "0.75, 0.10, NA, ");
+        assert_eq!(
+            out.column("d_n_unique").unwrap().as_i64().unwrap(),
+            &[2, 2, 0]
+        );
+        assert_eq!(col("n_sum"), "6, NA, NA, ");
+        assert_eq!(col("n_mean"), "2, NA, NA, ");
+        assert_eq!(col("n_median"), "2, NA, NA, ");
+        assert_eq!(col("n_first"), "1, NA, NA, ");
+
+        let df = csv_read_str_iter(
+            "x,99999999999999999999999999999999999999",
+            "k:string,d:decimal",
+        )
+        .unwrap();
+        let mut df2 = df.clone();
+        df2.append_line("x,1").unwrap();
+        assert!(df2
+            .group_by(&["k"])
+            .unwrap()
+            .agg(&[("d", Agg::Sum)])
+            .is_err());
+        assert!(df.group_by(&["k"]).unwrap().agg(&[("d", Agg::Sum)]).is_ok());
+    }
+}
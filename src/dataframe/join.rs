@@ -0,0 +1,617 @@
+use super::column::{CastOptions, Column, Dtype, TimeUnit, Value, VectorData};
+use super::concat::numeric_kind;
+use super::concat::{null_column, stack_columns, supertype};
+use super::groupby::{row_key, KeyPart};
+use super::sort::total_cmp;
+use super::temporal::parse_duration;
+use super::{DFError, DataFrame, Result};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JoinType {
+    Inner,
+    Left,
+    Right,
+    Full,
+    Semi, //rows of the left frame with a match, left columns only
+    Anti, //rows of the left frame without a match, left columns only
+    Cross,
+}
+
+//suffix goes on right columns whose name the left frame has. null keys match nothing
+//unless nulls_equal, as in SQL
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JoinOptions {
+    pub suffix: String,
+    pub nulls_equal: bool,
+}
+
+impl Default for JoinOptions {
+    fn default() -> JoinOptions {
+        JoinOptions {
+            suffix: String::from("_right"),
+            nulls_equal: false,
+        }
+    }
+}
+
+//which right row an as-of join picks for a left key: the last one at or before it,
+//the first one at or after it, or the closer of both with ties going backward
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum AsofStrategy {
+    #[default]
+    Backward,
+    Forward,
+    Nearest,
+}
+
+//tolerance is the largest key distance that still matches, in the units of the key, e.g. 5
+//for ints or 2s or 1d for datetimes. by columns have to be equal for a match
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AsofOptions {
+    pub strategy: AsofStrategy,
+    pub tolerance: Option<String>,
+    pub by: Vec<String>,
+    pub suffix: String,
+}
+
+impl Default for AsofOptions {
+    fn default() -> AsofOptions {
+        AsofOptions {
+            strategy: AsofStrategy::default(),
+            tolerance: None,
+            by: Vec::new(),
+            suffix: JoinOptions::default().suffix,
+        }
+    }
+}
+
+fn join_err(error_msg: String) -> Box<dyn std::error::Error> {
+    Box::new(DFError {
+        error_msg,
+        sub_errors: Vec::new(),
+    })
+}
+
+//key columns of both frames cast to a common dtype, so equal keys hash alike
+fn key_columns(
+    left: &DataFrame,
+    right: &DataFrame,
+    on: &[&str],
+) -> Result<(Vec<Column>, Vec<Column>)> {
+    let (mut lkeys, mut rkeys) = (Vec::new(), Vec::new());
+    for name in on.iter() {
+        let (l, r) = (left.column(name)?, right.column(name)?);
+        let dtype = supertype(l.dtype(), r.dtype()).ok_or_else(|| {
+            join_err(format!(
+                "key \"{}\" cannot join {} and {}",
+                name,
+                l.dtype(),
+                r.dtype()
+            ))
+        })?;
+        lkeys.push(l.cast(dtype, CastOptions::strict())?);
+        rkeys.push(r.cast(dtype, CastOptions::strict())?);
+    }
+    Ok((lkeys, rkeys))
+}
+
+fn has_null(cols: &[&dyn VectorData], idx: usize) -> bool {
+    cols.iter().any(|c| c.is_null(idx))
+}
+
+fn cmp_keys(a: &[&dyn VectorData], i: usize, b: &[&dyn VectorData], j: usize) -> Ordering {
+    a.iter()
+        .zip(b.iter())
+        .map(|(x, y)| total_cmp(&x.get_value(i), &y.get_value(j)))
+        .find(|o| o.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+//keys ascending without nulls, which merge_matches needs
+fn is_sorted(cols: &[&dyn VectorData], nrow: usize) -> bool {
+    (0..nrow).all(|i| !has_null(cols, i))
+        && (1..nrow).all(|i| cmp_keys(cols, i - 1, cols, i).is_le())
+}
+
+//right rows matching each left row, in right row order
+pub(crate) fn hash_matches(
+    left: &[&dyn VectorData],
+    right: &[&dyn VectorData],
+    nulls_equal: bool,
+) -> Vec<Vec<usize>> {
+    let (nleft, nright) = (left[0].len(), right[0].len());
+    let mut table: HashMap<Vec<KeyPart>, Vec<usize>> = HashMap::new();
+    for j in (0..nright).filter(|&j| nulls_equal || !has_null(right, j)) {
+        table.entry(row_key(right, j)).or_default().push(j);
+    }
+    (0..nleft)
+        .map(|i| {
+            if !nulls_equal && has_null(left, i) {
+                return Vec::new();
+            }
+            table.get(&row_key(left, i)).cloned().unwrap_or_default()
+        })
+        .collect()
+}
+
+//as hash_matches for keys sorted ascending on both sides, walking equal runs
+pub(crate) fn merge_matches(
+    left: &[&dyn VectorData],
+    right: &[&dyn VectorData],
+) -> Vec<Vec<usize>> {
+    let (nleft, nright) = (left[0].len(), right[0].len());
+    let mut matches = vec![Vec::new(); nleft];
+    let (mut i, mut j) = (0, 0);
+    while i < nleft && j < nright {
+        match cmp_keys(left, i, right, j) {
+            Ordering::Less => i += 1,
+            Ordering::Greater => j += 1,
+            Ordering::Equal => {
+                let j_end = (j..nright)
+                    .find(|&k| cmp_keys(left, i, right, k).is_ne())
+                    .unwrap_or(nright);
+                let i_end = (i..nleft)
+                    .find(|&k| cmp_keys(left, k, right, j).is_ne())
+                    .unwrap_or(nleft);
+                for m in matches[i..i_end].iter_mut() {
+                    m.extend(j..j_end);
+                }
+                (i, j) = (i_end, j_end);
+            }
+        }
+    }
+    matches
+}
+
+//as-of keys are ints, ticks of temporal dtypes or floats, they compare within one variant
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+enum AsofKey {
+    Int(i128),
+    Float(f64),
+}
+
+impl AsofKey {
+    fn from_value(value: Value) -> Option<AsofKey> {
+        match value {
+            Value::Int(x) | Value::Datetime(x, _) | Value::Duration(x, _) => {
+                Some(AsofKey::Int(x as i128))
+            }
+            Value::Date(x) => Some(AsofKey::Int(x as i128)),
+            Value::UInt(x) => Some(AsofKey::Int(x as i128)),
+            Value::Double(x) if !x.is_nan() => Some(AsofKey::Float(x)),
+            _ => None,
+        }
+    }
+
+    fn distance(self, other: AsofKey) -> AsofKey {
+        match (self, other) {
+            (AsofKey::Int(a), AsofKey::Int(b)) => AsofKey::Int((a - b).abs()),
+            (AsofKey::Float(a), AsofKey::Float(b)) => AsofKey::Float((a - b).abs()),
+            _ => AsofKey::Float(f64::INFINITY),
+        }
+    }
+}
+
+//tolerance text in the units of a key of dtype, dates take days or a duration like 2d
+fn parse_tolerance(x: &str, dtype: Dtype) -> Result<AsofKey> {
+    let x = x.trim();
+    let tolerance = match dtype.non_nullable() {
+        Dtype::ColDatetime(unit) | Dtype::ColDuration(unit) => {
+            AsofKey::Int(parse_duration(x, unit)? as i128)
+        }
+        Dtype::ColDate => match x.parse::<i128>() {
+            Ok(days) => AsofKey::Int(days),
+            Err(_) => {
+                let ms = parse_duration(x, TimeUnit::Milliseconds)?;
+                AsofKey::Int((ms / (86_400 * 1000)) as i128)
+            }
+        },
+        Dtype::ColDouble | Dtype::ColF64 => AsofKey::Float(x.parse::<f64>()?),
+        _ => AsofKey::Int(x.parse::<i128>()?),
+    };
+    let negative = match tolerance {
+        AsofKey::Int(t) => t < 0,
+        AsofKey::Float(t) => t.is_nan() || t < 0.0,
+    };
+    if negative {
+        return Err(join_err(format!("tolerance \"{}\" is negative", x)));
+    }
+    Ok(tolerance)
+}
+
+//the right row for a left key among right rows sorted by key
+fn asof_pick(
+    key: AsofKey,
+    sorted: &[(AsofKey, usize)],
+    strategy: AsofStrategy,
+    tolerance: Option<AsofKey>,
+) -> Option<usize> {
+    let after = sorted.partition_point(|(k, _)| *k <= key);
+    let backward = after.checked_sub(1).map(|p| sorted[p]);
+    let forward = sorted[sorted.partition_point(|(k, _)| *k < key)..]
+        .first()
+        .copied();
+    let (k, j) = match strategy {
+        AsofStrategy::Backward => backward?,
+        AsofStrategy::Forward => forward?,
+        AsofStrategy::Nearest => match (backward, forward) {
+            (Some(b), Some(f)) if f.0.distance(key) < b.0.distance(key) => f,
+            (Some(b), _) => b,
+            (None, f) => f?,
+        },
+    };
+    match tolerance {
+        Some(t) if k.distance(key) > t => None,
+        _ => Some(j),
+    }
+}
+
+//rows of col at indices, None rows are null
+pub(crate) fn take_opt(col: &Column, indices: &[Option<usize>]) -> Result<Column> {
+    if indices.iter().all(|i| i.is_some()) {
+        let indices: Vec<usize> = indices.iter().flatten().copied().collect();
+        return Ok(col.take(&indices));
+    }
+    let nrow = col.data.len();
+    let padded = stack_columns(col.clone(), &null_column(col, 1)?)?;
+    let indices: Vec<usize> = indices.iter().map(|i| i.unwrap_or(nrow)).collect();
+    Ok(padded.take(&indices))
+}
+
+impl DataFrame {
+    //rows of self and other combined where the key columns on match, with default options,
+    //e.g. facts.join(&dims, &["id"], JoinType::Left)
+    pub fn join(&self, other: &DataFrame, on: &[&str], how: JoinType) -> Result<DataFrame> {
+        self.join_with(other, on, how, &JoinOptions::default())
+    }
+
+    //the key columns come first from self, followed by the other columns of self and of other.
+    //inner and left joins keep the row order of self, right joins the row order of other.
+    //keys sorted ascending on both sides are merged, others matched by a hash table
+    pub fn join_with(
+        &self,
+        other: &DataFrame,
+        on: &[&str],
+        how: JoinType,
+        options: &JoinOptions,
+    ) -> Result<DataFrame> {
+        let (nleft, nright) = (self.shape().0, other.shape().0);
+        if how == JoinType::Cross {
+            if !on.is_empty() {
+                return Err(join_err(String::from("a cross join has no keys")));
+            }
+            let pairs: Vec<(Option<usize>, Option<usize>)> = (0..nleft)
+                .flat_map(|i| (0..nright).map(move |j| (Some(i), Some(j))))
+                .collect();
+            return self.join_columns(other, &[], None, &pairs, options);
+        }
+        if on.is_empty() {
+            return Err(join_err(format!("a {:?} join needs keys", how)));
+        }
+
+        let (lkeys, rkeys) = key_columns(self, other, on)?;
+        let l: Vec<&dyn VectorData> = lkeys.iter().map(|c| c.data.as_ref()).collect();
+        let r: Vec<&dyn VectorData> = rkeys.iter().map(|c| c.data.as_ref()).collect();
+        let matches = if is_sorted(&l, nleft) && is_sorted(&r, nright) {
+            merge_matches(&l, &r)
+        } else {
+            hash_matches(&l, &r, options.nulls_equal)
+        };
+
+        let mut pairs: Vec<(Option<usize>, Option<usize>)> = Vec::new();
+        match how {
+            JoinType::Semi | JoinType::Anti => {
+                let keep = how == JoinType::Semi;
+                let rows: Vec<usize> = (0..nleft)
+                    .filter(|&i| matches[i].is_empty() != keep)
+                    .collect();
+                return self.take(&rows);
+            }
+            JoinType::Right => {
+                let mut by_right = vec![Vec::new(); nright];
+                for (i, m) in matches.iter().enumerate() {
+                    m.iter().for_each(|&j| by_right[j].push(i));
+                }
+                for (j, m) in by_right.iter().enumerate() {
+                    if m.is_empty() {
+                        pairs.push((None, Some(j)));
+                    }
+                    pairs.extend(m.iter().map(|&i| (Some(i), Some(j))));
+                }
+            }
+            _ => {
+                let mut matched = vec![false; nright];
+                for (i, m) in matches.iter().enumerate() {
+                    if m.is_empty() && how != JoinType::Inner {
+                        pairs.push((Some(i), None));
+                    }
+                    for &j in m.iter() {
+                        matched[j] = true;
+                        pairs.push((Some(i), Some(j)));
+                    }
+                }
+                if how == JoinType::Full {
+                    pairs.extend(
+                        (0..nright)
+                            .filter(|&j| !matched[j])
+                            .map(|j| (None, Some(j))),
+                    );
+                }
+            }
+        }
+        self.join_columns(other, on, Some((lkeys, rkeys)), &pairs, options)
+    }
+
+    //each row of self with the row of other whose key on is closest by the strategy, e.g.
+    //the latest quote at or before each trade. keys need not be sorted, rows without a
+    //match get nulls in the columns of other
+    pub fn join_asof(
+        &self,
+        other: &DataFrame,
+        on: &str,
+        options: &AsofOptions,
+    ) -> Result<DataFrame> {
+        let (lkey, rkey) = key_columns(self, other, &[on])?;
+        let dtype = lkey[0].dtype().non_nullable();
+        let temporal = matches!(
+            dtype,
+            Dtype::ColDate | Dtype::ColDatetime(_) | Dtype::ColDuration(_)
+        );
+        if numeric_kind(dtype).is_none() && !temporal {
+            return Err(join_err(format!(
+                "as-of key \"{}\" is {}, not numeric or temporal",
+                on, dtype
+            )));
+        }
+        let tolerance = match &options.tolerance {
+            Some(x) => Some(parse_tolerance(x, dtype)?),
+            None => None,
+        };
+
+        let by: Vec<&str> = options.by.iter().map(|s| &s[..]).collect();
+        let (lby, rby) = key_columns(self, other, &by)?;
+        let lby: Vec<&dyn VectorData> = lby.iter().map(|c| c.data.as_ref()).collect();
+        let rby: Vec<&dyn VectorData> = rby.iter().map(|c| c.data.as_ref()).collect();
+
+        //right rows by their by key, sorted by the as-of key, null keys never match
+        let mut groups: HashMap<Vec<KeyPart>, Vec<(AsofKey, usize)>> = HashMap::new();
+        for j in (0..other.shape().0).filter(|&j| !has_null(&rby, j)) {
+            if let Some(k) = AsofKey::from_value(rkey[0].data.get_value(j)) {
+                groups.entry(row_key(&rby, j)).or_default().push((k, j));
+            }
+        }
+        for rows in groups.values_mut() {
+            rows.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+        }
+
+        let pairs: Vec<(Option<usize>, Option<usize>)> = (0..self.shape().0)
+            .map(|i| {
+                let key = AsofKey::from_value(lkey[0].data.get_value(i));
+                let group = groups.get(&row_key(&lby, i));
+                let j = match (key, group) {
+                    (Some(k), Some(rows)) if !has_null(&lby, i) => {
+                        asof_pick(k, rows, options.strategy, tolerance)
+                    }
+                    _ => None,
+                };
+                (Some(i), j)
+            })
+            .collect();
+
+        let mut on_by = vec![on];
+        on_by.extend(by.iter());
+        let (lkeys, rkeys) = key_columns(self, other, &on_by)?;
+        let join_options = JoinOptions {
+            suffix: options.suffix.clone(),
+            ..JoinOptions::default()
+        };
+        self.join_columns(other, &on_by, Some((lkeys, rkeys)), &pairs, &join_options)
+    }
+
+    //the output columns of the row pairs, keys are taken from whichever side has the row
+    fn join_columns(
+        &self,
+        other: &DataFrame,
+        on: &[&str],
+        keys: Option<(Vec<Column>, Vec<Column>)>,
+        pairs: &[(Option<usize>, Option<usize>)],
+        options: &JoinOptions,
+    ) -> Result<DataFrame> {
+        let nleft = self.shape().0;
+        let left_rows: Vec<Option<usize>> = pairs.iter().map(|p| p.0).collect();
+        let right_rows: Vec<Option<usize>> = pairs.iter().map(|p| p.1).collect();
+        let is_key = |c: &Column| c.name.as_deref().is_some_and(|n| on.contains(&n));
+
+        let mut data = Vec::new();
+        if let Some((lkeys, rkeys)) = keys {
+            let rows: Vec<usize> = pairs
+                .iter()
+                .map(|p| p.0.unwrap_or_else(|| nleft + p.1.unwrap_or_default()))
+                .collect();
+            for (mut key, rkey) in lkeys.into_iter().zip(rkeys.iter()) {
+                key.data.extend(rkey.data.as_ref())?;
+                data.push(key.take(&rows));
+            }
+        }
+        for col in self.data.iter().filter(|c| !is_key(c)) {
+            data.push(take_opt(col, &left_rows)?);
+        }
+        for col in other.data.iter().filter(|c| !is_key(c)) {
+            let mut out = take_opt(col, &right_rows)?;
+            if let Some(name) = &col.name {
+                if self.position_of(name).is_ok() {
+                    out.name = Some(format!("{}{}", name, options.suffix));
+                }
+            }
+            data.push(out);
+        }
+        DataFrame::from_columns(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataframe::csv_read_str_iter;
+
+    fn joined(df: &DataFrame, name: &str) -> String {
+        df.column(name).unwrap().data.to_string()
+    }
+
+    #[test]
+    fn join_types() {
+        let facts =
+            csv_read_str_iter("3,10\n1,20\n,30\n2,40\n1,50", "id:intNullable,amount:int").unwrap();
+        let dims = csv_read_str_iter(
+            "1,a,x\n4,d,y\n1,b,z\n3,c,w",
+            "id:i64,name:string,amount:string",
+        )
+        .unwrap();
+
+        let df = facts.join(&dims, &["id"], JoinType::Inner).unwrap();
+        assert_eq!(
+            df.names(),
+            vec![
+                Some("id"),
+                Some("amount"),
+                Some("name"),
+                Some("amount_right")
+            ]
+        );
+        assert_eq!(joined(&df, "id"), "3, 1, 1, 1, 1, ");
+        assert_eq!(joined(&df, "amount"), "10, 20, 20, 50, 50, ");
+        assert_eq!(joined(&df, "name"), "c, a, b, a, b, ");
+
+        let df = facts.join(&dims, &["id"], JoinType::Left).unwrap();
+        assert_eq!(joined(&df, "id"), "3, 1, 1, NA, 2, 1, 1, ");
+        assert_eq!(joined(&df, "name"), "c, a, b, NA, NA, a, b, ");
+
+        let df = facts.join(&dims, &["id"], JoinType::Right).unwrap();
+        assert_eq!(joined(&df, "id"), "1, 1, 4, 1, 1, 3, ");
+        assert_eq!(joined(&df, "amount"), "20, 50, NA, 20, 50, 10, ");
+
+        let df = facts.join(&dims, &["id"], JoinType::Full).unwrap();
+        assert_eq!(df.shape(), (8, 4));
+        assert_eq!(joined(&df, "name"), "c, a, b, NA, NA, a, b, d, ");
+
+        let df = facts.join(&dims, &["id"], JoinType::Semi).unwrap();
+        assert_eq!(joined(&df, "amount"), "10, 20, 50, ");
+        let df = facts.join(&dims, &["id"], JoinType::Anti).unwrap();
+        assert_eq!(joined(&df, "amount"), "30, 40, ");
+
+        let options = JoinOptions {
+            suffix: String::from("_dim"),
+            ..JoinOptions::default()
+        };
+        let df = facts
+            .join_with(&dims, &[], JoinType::Cross, &options)
+            .unwrap();
+        assert_eq!(df.shape(), (20, 5));
+        assert_eq!(df.names()[2], Some("id_dim"));
+        assert!(facts.join(&dims, &["id"], JoinType::Cross).is_err());
+        assert!(facts.join(&dims, &["name"], JoinType::Inner).is_err());
+        assert!(facts
+            .join(&dims, &["id", "amount"], JoinType::Inner)
+            .is_err());
+    }
+
+    #[test]
+    fn join_null_keys_and_multiple_keys() {
+        let a = csv_read_str_iter("x,,1\nx,1,2\ny,1,3", "k:string,n:intNullable,v:int").unwrap();
+        let b = csv_read_str_iter("x,,10\ny,1,30\nx,1,20", "k:string,n:intNullable,w:int").unwrap();
+        let df = a.join(&b, &["k", "n"], JoinType::Inner).unwrap();
+        assert_eq!(joined(&df, "w"), "20, 30, ");
+
+        let options = JoinOptions {
+            nulls_equal: true,
+            ..JoinOptions::default()
+        };
+        let df = a
+            .join_with(&b, &["k", "n"], JoinType::Inner, &options)
+            .unwrap();
+        assert_eq!(joined(&df, "w"), "10, 20, 30, ");
+    }
+
+    #[test]
+    fn asof_join() {
+        let trades = csv_read_str_iter(
+            "2021-03-04 10:00:02,a,1\n2021-03-04 10:00:00,b,2\n2021-03-04 10:00:10,a,3\n,a,4",
+            "t:datetime[unit=ms]?,sym:string,id:int",
+        )
+        .unwrap();
+        let quotes = [
+            "2021-03-04 10:00:01,a,1.5",
+            "2021-03-04 10:00:00,b,9.5",
+            "2021-03-04 10:00:03,a,2.5",
+            "2021-03-04 09:59:00,a,0.5",
+        ];
+        let quotes = csv_read_str_iter(
+            &quotes.join("\n"),
+            "t:datetime[unit=ms],sym:string,px:double",
+        )
+        .unwrap();
+
+        let mut options = AsofOptions {
+            by: vec![String::from("sym")],
+            ..AsofOptions::default()
+        };
+        let df = trades.join_asof(&quotes, "t", &options).unwrap();
+        assert_eq!(
+            df.names(),
+            vec![Some("t"), Some("sym"), Some("id"), Some("px")]
+        );
+        assert_eq!(joined(&df, "px"), "1.5, 9.5, 2.5, NA, ");
+
+        options.strategy = AsofStrategy::Forward;
+        let df = trades.join_asof(&quotes, "t", &options).unwrap();
+        assert_eq!(joined(&df, "px"), "2.5, 9.5, NA, NA, ");
+
+        options.strategy = AsofStrategy::Nearest;
+        options.tolerance = Some(String::from("5s"));
+        let df = trades.join_asof(&quotes, "t", &options).unwrap();
+        assert_eq!(joined(&df, "px"), "1.5, 9.5, NA, NA, ");
+
+        //without by, b matches quotes of a too
+        let df = trades
+            .join_asof(&quotes, "t", &AsofOptions::default())
+            .unwrap();
+        assert_eq!(joined(&df, "px"), "1.5, 9.5, 2.5, NA, ");
+        assert_eq!(df.names()[3], Some("sym_right"));
+
+        options.tolerance = Some(String::from("-1s"));
+        assert!(trades.join_asof(&quotes, "t", &options).is_err());
+        assert!(trades
+            .join_asof(&quotes, "sym", &AsofOptions::default())
+            .is_err());
+
+        let a = csv_read_str_iter("1\n5\n9", "k:int").unwrap();
+        let b = csv_read_str_iter("4,x\n6,y", "k:i64,v:string").unwrap();
+        let options = AsofOptions {
+            strategy: AsofStrategy::Nearest,
+            ..AsofOptions::default()
+        };
+        let df = a.join_asof(&b, "k", &options).unwrap();
+        assert_eq!(joined(&df, "v"), "x, x, y, ");
+    }
+
+    #[test]
+    fn merge_matches_hash() {
+        let a = csv_read_str_iter("1\n2\n2\n5\n7", "k:int").unwrap();
+        let b = csv_read_str_iter("2\n2\n3\n5\n8", "k:i64").unwrap();
+        let (lkeys, rkeys) = key_columns(&a, &b, &["k"]).unwrap();
+        let l: Vec<&dyn VectorData> = lkeys.iter().map(|c| c.data.as_ref()).collect();
+        let r: Vec<&dyn VectorData> = rkeys.iter().map(|c| c.data.as_ref()).collect();
+        assert!(is_sorted(&l, 5) && is_sorted(&r, 5));
+        let merged = merge_matches(&l, &r);
+        assert_eq!(
+            merged,
+            vec![vec![], vec![0, 1], vec![0, 1], vec![3], vec![]]
+        );
+        assert_eq!(merged, hash_matches(&l, &r, false));
+
+        let df = a.join(&b, &["k"], JoinType::Full).unwrap();
+        assert_eq!(joined(&df, "k"), "1, 2, 2, 2, 2, 5, 7, 3, 8, ");
+    }
+}
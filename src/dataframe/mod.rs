@@ -1,9 +1,26 @@
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::{self, BufRead, Read};
 use std::path::Path;
 
+mod binary;
+mod cast;
+mod categorical;
 pub mod column;
+mod concat;
+pub mod constraints;
+pub mod decimal;
+mod dedup;
+mod filter;
+mod groupby;
+mod join;
 pub mod lineparser;
+mod nested;
+mod reshape;
+pub mod schema;
+mod sort;
+mod sqlite;
+pub mod temporal;
 use std::fmt;
 
 use std::str;
@@ -13,6 +30,15 @@ type Result<T> = std::result::Result<T, Box<dyn error::Error>>;
 
 use serde::{Deserialize, Serialize};
 
+pub use concat::{supertype, ColumnPolicy, ConcatOptions};
+pub use constraints::{Constraint, Constraints, ValidationReport, Violation};
+pub use dedup::Keep;
+pub use filter::Row;
+pub use groupby::{Agg, GroupBy};
+pub use join::{AsofOptions, AsofStrategy, JoinOptions, JoinType};
+pub use schema::{Field, Schema, ToSchema};
+pub use sort::{NullPlacement, SortOrder};
+
 #[derive(Debug)]
 pub struct DFError {
     error_msg: String,
@@ -32,6 +58,10 @@ impl error::Error for DFError {}
 #[derive(serde::Serialize, Deserialize)]
 pub struct DataFrame {
     pub data: Vec<column::Column>,
+    //position by column name, rebuilt by the methods adding, removing or renaming columns.
+    //lookups check the hit and fall back to a scan if data was changed directly
+    #[serde(skip)]
+    index: HashMap<String, usize>,
 }
 
 impl fmt::Display for DataFrame {
@@ -54,67 +84,53 @@ impl Clone for DataFrame {
     fn clone(&self) -> Self {
         DataFrame {
             data: self.data.to_vec(),
+            index: self.index.clone(),
         }
     }
 }
 
 impl DataFrame {
-    pub fn new(schema: &str) -> Result<DataFrame> {
-        //split schema syntax str by
-        let column_separators: &[char] = &[',', ';', '\n'][..];
-        let token_separators: &[char] = &[':'][..];
-
-        // iterate descriptions of columns, and nested iterate tokens within
-        let lcase_schema = schema.to_lowercase();
-        let col_descr_iters = lcase_schema
-            .split(column_separators)
-            .map(|x| x.rsplit(token_separators))
-            .enumerate();
-
-        //iterator of parsed columns
-        let col_parsed_result = col_descr_iters.map(|(i_col, mut this_col_dscr_iter)| {
-            //rsplit yields the last token after : or the only token if no :
-            let type_token = this_col_dscr_iter
-                .next()
-                .expect("whaat, split cannot yield None");
-            let dtype = column::Dtype::from_str_to_res(&type_token[..])?;
-
-            //..any other optionally token is the name
-            let name_token = this_col_dscr_iter.next().map(String::from);
-
-            //error if more tokens for one column
-            if let Some(third_token) = this_col_dscr_iter.next() {
-                Err(column::ColError {
-                    errorcode: column::ColErrorcode::SchemaSyntax,
-                    error_msg: format!(
-                        "unexpected third token \"{}\" for column descr No. {}",
-                        third_token, i_col
-                    ),
-                })?;
-            }
+    //empty data frame of a schema string like "a:int,b:string" or a Schema
+    pub fn new<S: ToSchema + ?Sized>(schema: &S) -> Result<DataFrame> {
+        DataFrame::from_schema(&schema.to_schema()?)
+    }
 
-            Ok(column::Column::new(name_token, dtype))
-        });
+    //as new, but the int and double aliases resolve to 64-bit types when width is Wide
+    pub fn new_with_width(schema: &str, width: column::AliasWidth) -> Result<DataFrame> {
+        DataFrame::from_schema(&Schema::parse_with_width(schema, width)?)
+    }
 
-        //collect parsed results in values and errors
-        let (values, errors): (Vec<_>, Vec<_>) =
-            col_parsed_result.partition(|result| result.is_ok());
+    pub fn from_schema(schema: &Schema) -> Result<DataFrame> {
+        DataFrame::from_columns(schema.to_columns()?)
+    }
 
-        //downcast'isch Result to error
-        let errors: Vec<Box<dyn std::error::Error>> =
-            errors.into_iter().flat_map(Result::err).collect();
-        if !errors.is_empty() {
-            Err(DFError {
-                error_msg: "failed to parse schema".to_string(),
-                sub_errors: errors,
-            })?;
-        }
+    //data frame of the columns, an error if names repeat or lengths differ
+    pub fn from_columns(data: Vec<column::Column>) -> Result<DataFrame> {
+        check_columns(&data)?;
+        let mut df = DataFrame {
+            data,
+            index: HashMap::new(),
+        };
+        df.reindex();
+        Ok(df)
+    }
+
+    //schema of the columns, including their metadata
+    pub fn schema(&self) -> Schema {
+        Schema::new(self.data.iter().map(Field::from_column).collect())
+    }
 
-        //if here all good, instanciate data.frame with all good columns
-        let values = values.into_iter().flat_map(Result::ok).collect();
-        Ok(DataFrame { data: values })
+    //empty data frame of a .json or .toml schema document, or a file with a schema string
+    pub fn from_schema_file<P: AsRef<Path>>(path: P) -> Result<DataFrame> {
+        DataFrame::from_schema(&Schema::from_file(path)?)
     }
 
+    //export the schema for versioning, the format follows the file extension
+    pub fn write_schema_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.schema().write_file(path)
+    }
+
+    //the append is all or nothing, cells pushed before an error are dropped again
     pub fn append_line(&mut self, i_line: &str) -> Result<()> {
         if i_line.len() == 0 {
             return Ok(());
@@ -122,35 +138,65 @@ impl DataFrame {
 
         //split by delimiter, iterator of segments
         let mut line_iter = i_line.split(',');
+        let (nrow, _) = self.shape();
 
         //loop over columns in data frame
-        for i_column in 0..self.data.len() {
+        let pushed = self.data.iter_mut().try_for_each(|column| {
             //consume str value from line
             let cell_str = line_iter.next().unwrap_or("").trim();
 
             //push into column, appropriate parsing applied if columns are e.g. i32, f32 vectors.
-            self.data[i_column].data.push_from_str(cell_str)?;
+            column.push_from_str(cell_str)
+        });
+        if let Err(e) = pushed {
+            self.truncate(nrow);
+            return Err(e);
         }
-        Ok(())
+        self.check_appended(nrow)
     }
 
     fn fill_remaining_row(&mut self, last_col: usize, crit: usize) -> Result<()> {
         if last_col < crit {
-            ((last_col + 1)..self.data.len()).try_for_each(|j| self.data[j].data.push_from_str(""))
+            ((last_col + 1)..self.data.len()).try_for_each(|j| self.data[j].push_from_str(""))
         } else {
             Ok(())
         }
     }
 
+    //the append is all or nothing, rows pushed before an error are dropped again
     pub fn append_str(&mut self, text: &str) -> Result<()> {
+        //a frame without columns has no cells to fill
+        let Some(crit) = self.data.len().checked_sub(1) else {
+            if text.is_empty() {
+                return Ok(());
+            }
+            return Err(Box::new(DFError {
+                error_msg: String::from("cannot append rows to a frame without columns"),
+                sub_errors: Vec::new(),
+            }));
+        };
+        let (nrow, _) = self.shape();
+        if let Err(e) = self.push_csv_str(text, crit) {
+            self.truncate(nrow);
+            return Err(e);
+        }
+        self.check_appended(nrow)
+    }
+
+    //the cells of text into the columns, crit is the index of the last column
+    fn push_csv_str(&mut self, text: &str, crit: usize) -> Result<()> {
         let csvstr = lineparser::CsvStr::new(text, 0, 0);
         let mut last_row: usize = 0;
         let mut last_col: usize = 0;
-        let crit = self.data.len() - 1;
-
         for i in csvstr {
+            if i.col > crit {
+                return Err(Box::new(DFError {
+                    error_msg: format!("line {} has more than {} cells", i.row + 1, crit + 1),
+                    sub_errors: Vec::new(),
+                }));
+            }
             //fill in a cell
-            self.data[i.col].data.push_from_str(i.text)?;
+            self.data[i.col].push_from_str(i.text)?;
 
             //check if new row
             if i.row != last_row {
@@ -160,8 +206,7 @@ impl DataFrame {
             last_col = i.col;
             last_row = i.row;
         }
-        self.fill_remaining_row(last_col, crit)?;
-        Ok(())
+        self.fill_remaining_row(last_col, crit)
     }
 
     #[allow(dead_code)]
@@ -171,6 +216,114 @@ impl DataFrame {
         }
     }
 
+    fn reindex(&mut self) {
+        self.index.clear();
+        for (i, col) in self.data.iter().enumerate() {
+            if let Some(name) = &col.name {
+                self.index.entry(name.clone()).or_insert(i);
+            }
+        }
+    }
+
+    fn position_of(&self, name: &str) -> Result<usize> {
+        let is_at = |i: &usize| {
+            self.data
+                .get(*i)
+                .is_some_and(|c| c.name.as_deref() == Some(name))
+        };
+        match self.index.get(name) {
+            Some(i) if is_at(i) => Ok(*i),
+            _ => (0..self.data.len()).find(is_at).ok_or_else(|| {
+                Box::new(DFError {
+                    error_msg: format!("no column named \"{}\"", name),
+                    sub_errors: Vec::new(),
+                }) as Box<dyn error::Error>
+            }),
+        }
+    }
+
+    //column by name, e.g. df.column("a")?.as_i32()?
+    pub fn column(&self, name: &str) -> Result<&column::Column> {
+        Ok(&self.data[self.position_of(name)?])
+    }
+
+    pub fn column_mut(&mut self, name: &str) -> Result<&mut column::Column> {
+        let i = self.position_of(name)?;
+        Ok(&mut self.data[i])
+    }
+
+    //names of the columns in order, unnamed columns are None
+    pub fn names(&self) -> Vec<Option<&str>> {
+        self.data.iter().map(|c| c.name.as_deref()).collect()
+    }
+
+    //new data frame of copies of the columns in the given order
+    pub fn select(&self, names: &[&str]) -> Result<DataFrame> {
+        let data = names
+            .iter()
+            .map(|name| Ok(self.data[self.position_of(name)?].clone()))
+            .collect::<Result<Vec<column::Column>>>()?;
+        DataFrame::from_columns(data)
+    }
+
+    //remove the columns, nothing is removed if one of them does not exist
+    pub fn drop(&mut self, names: &[&str]) -> Result<()> {
+        let positions = names
+            .iter()
+            .map(|name| self.position_of(name))
+            .collect::<Result<HashSet<usize>>>()?;
+        self.data = std::mem::take(&mut self.data)
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| !positions.contains(i))
+            .map(|(_, col)| col)
+            .collect();
+        self.reindex();
+        Ok(())
+    }
+
+    pub fn rename(&mut self, old: &str, new: &str) -> Result<()> {
+        let i = self.position_of(old)?;
+        if old != new && self.position_of(new).is_ok() {
+            Err(DFError {
+                error_msg: format!("cannot rename \"{}\", column \"{}\" exists", old, new),
+                sub_errors: Vec::new(),
+            })?;
+        }
+        self.data[i].name = Some(new.to_string());
+        self.reindex();
+        Ok(())
+    }
+
+    //replace the column of the same name or add it as the last column,
+    //its length must match the other columns
+    pub fn with_column(&mut self, col: column::Column) -> Result<()> {
+        let i = col.name.as_deref().and_then(|n| self.position_of(n).ok());
+        let nrow = self
+            .data
+            .iter()
+            .enumerate()
+            .find(|(j, _)| Some(*j) != i)
+            .map(|(_, c)| c.data.len());
+        if nrow.is_some_and(|n| n != col.data.len()) {
+            Err(DFError {
+                error_msg: format!(
+                    "column \"{}\" has {} rows, the data frame {}",
+                    col.name.as_deref().unwrap_or("None"),
+                    col.data.len(),
+                    nrow.unwrap_or_default()
+                ),
+                sub_errors: Vec::new(),
+            })?;
+        }
+        match i {
+            Some(i) => self.data[i] = col,
+            None => self.data.push(col),
+        }
+        self.reindex();
+        Ok(())
+    }
+
     pub fn shape(&self) -> (usize, usize) {
         let ncol = self.data.len();
         if ncol == 0 {
@@ -180,12 +333,71 @@ impl DataFrame {
             return (nrow, ncol);
         }
     }
+
+    //keep the first len rows
+    pub fn truncate(&mut self, len: usize) {
+        self.data.iter_mut().for_each(|c| c.data.truncate(len));
+    }
 }
 
-pub fn csv_read_file_iter2(file_name: &str, schema_str: &str) -> Result<DataFrame> {
-    let x = String::from(schema_str);
-    //make empty data frame by schema str
-    let mut df = DataFrame::new(&x)?;
+//named columns have distinct names and all columns the same length
+fn check_columns(data: &[column::Column]) -> Result<()> {
+    let mut errors: Vec<Box<dyn error::Error>> = Vec::new();
+    let mut seen = HashSet::new();
+    for name in data.iter().filter_map(|c| c.name.as_deref()) {
+        if !seen.insert(name) {
+            errors.push(format!("column name \"{}\" is not unique", name).into());
+        }
+    }
+    if let Some(first) = data.first() {
+        let nrow = first.data.len();
+        for (i, col) in data.iter().enumerate() {
+            if col.data.len() != nrow {
+                errors.push(
+                    format!(
+                        "column No. {} has {} rows, column No. 0 has {}",
+                        i,
+                        col.data.len(),
+                        nrow
+                    )
+                    .into(),
+                );
+            }
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(Box::new(DFError {
+            error_msg: String::from("invalid columns"),
+            sub_errors: errors,
+        }))
+    }
+}
+
+//split s by any of seps, ignoring separators enclosed in [ ]
+fn split_outside_brackets<'a>(s: &'a str, seps: &[char]) -> Vec<&'a str> {
+    let mut out = Vec::new();
+    let mut depth: usize = 0;
+    let mut start: usize = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            _ if depth == 0 && seps.contains(&c) => {
+                out.push(&s[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => (),
+        }
+    }
+    out.push(&s[start..]);
+    out
+}
+
+pub fn csv_read_file_iter2<S: ToSchema + ?Sized>(file_name: &str, schema: &S) -> Result<DataFrame> {
+    //make empty data frame by schema
+    let mut df = DataFrame::new(schema)?;
     let file = File::open(file_name)?;
     let mut buffer = String::new();
     io::BufReader::with_capacity(256000, file).read_to_string(&mut buffer)?;
@@ -194,18 +406,16 @@ pub fn csv_read_file_iter2(file_name: &str, schema_str: &str) -> Result<DataFram
     Ok(df)
 }
 
-pub fn csv_read_str(csv_str: &str, schema_str: &str) -> Result<DataFrame> {
-    let x = String::from(schema_str);
-    let mut df = DataFrame::new(&x)?;
+pub fn csv_read_str<S: ToSchema + ?Sized>(csv_str: &str, schema: &S) -> Result<DataFrame> {
+    let mut df = DataFrame::new(schema)?;
     csv_str.lines().try_for_each(|line| df.append_line(line))?;
     Ok(df)
 }
 
 #[allow(dead_code)]
-pub fn csv_read_str_iter(csv_str: &str, schema_str: &str) -> Result<DataFrame> {
-    let x = String::from(schema_str);
-    //make empty data frame by schema str
-    let mut df = DataFrame::new(&x)?;
+pub fn csv_read_str_iter<S: ToSchema + ?Sized>(csv_str: &str, schema: &S) -> Result<DataFrame> {
+    //make empty data frame by schema
+    let mut df = DataFrame::new(schema)?;
 
     df.append_str(csv_str)?;
 
@@ -216,14 +426,6 @@ pub fn csv_read_str_iter(csv_str: &str, schema_str: &str) -> Result<DataFrame> {
 mod tests {
     use super::*;
 
-    macro_rules! downcast_any_to {
-        ($x:expr, $y:ty) => {
-            $x.as_any()
-                .downcast_ref::<$y>()
-                .expect("not expected column type")
-        };
-    }
-
     #[test]
     fn test_csv_read_str() {
         //read this csv by this schema
@@ -234,24 +436,20 @@ mod tests {
         println!("iter df looks like ({})", df);
 
         //check ColInt
-        let a_ref_col = Box::new(column::ColInt { data: vec![1, 10] });
-        let a_act_col: &column::ColInt = downcast_any_to!(&df.data[0].data, column::ColInt);
-        assert_eq!(*a_act_col.data, *a_ref_col.data);
+        assert_eq!(df.column("a").unwrap().as_i32().unwrap(), &[1, 10]);
 
-        //check ColDoubleNullablet
-        let b_ref_col = Box::new(column::ColDoubleNullable {
-            data: vec![Some(2.2), Some(20.20)],
-        });
-        let b_act_col: &column::ColDoubleNullable =
-            downcast_any_to!(&df.data[1].data, column::ColDoubleNullable);
-        assert_eq!(*b_act_col.data, *b_ref_col.data);
+        //check ColDoubleNullable
+        let b_act_col: Vec<_> = df.column("b").unwrap().as_f32_opt().unwrap().collect();
+        assert_eq!(b_act_col, vec![Some(2.2), Some(20.20)]);
 
         //check ColString
-        let d_ref_col = Box::new(column::ColString {
-            data: vec![String::from("four"), String::from("fourty")],
-        });
-        let d_act_col: &column::ColString = downcast_any_to!(&df.data[3].data, column::ColString);
-        assert_eq!(*d_act_col.data, *d_ref_col.data);
+        let d_act_col: Vec<_> = df
+            .column("someothername")
+            .unwrap()
+            .as_str_iter()
+            .unwrap()
+            .collect();
+        assert_eq!(d_act_col, vec!["four", "fourty"]);
     }
 
     #[test]
@@ -264,24 +462,20 @@ mod tests {
         println!("iter df looks like ({})", df);
 
         //check ColInt
-        let a_ref_col = Box::new(column::ColInt { data: vec![1, 10] });
-        let a_act_col: &column::ColInt = downcast_any_to!(&df.data[0].data, column::ColInt);
-        assert_eq!(*a_act_col.data, *a_ref_col.data);
+        assert_eq!(df.column("a").unwrap().as_i32().unwrap(), &[1, 10]);
 
-        //check ColDoubleNullablet
-        let b_ref_col = Box::new(column::ColDoubleNullable {
-            data: vec![Some(2.2), Some(20.20)],
-        });
-        let b_act_col: &column::ColDoubleNullable =
-            downcast_any_to!(&df.data[1].data, column::ColDoubleNullable);
-        assert_eq!(*b_act_col.data, *b_ref_col.data);
+        //check ColDoubleNullable
+        let b_act_col: Vec<_> = df.column("b").unwrap().as_f32_opt().unwrap().collect();
+        assert_eq!(b_act_col, vec![Some(2.2), Some(20.20)]);
 
         //check ColString
-        let d_ref_col = Box::new(column::ColString {
-            data: vec![String::from("four"), String::from("fourty")],
-        });
-        let d_act_col: &column::ColString = downcast_any_to!(&df.data[3].data, column::ColString);
-        assert_eq!(*d_act_col.data, *d_ref_col.data);
+        let d_act_col: Vec<_> = df
+            .column("someothername")
+            .unwrap()
+            .as_str_iter()
+            .unwrap()
+            .collect();
+        assert_eq!(d_act_col, vec!["four", "fourty"]);
     }
 
     #[test]
@@ -294,24 +488,46 @@ mod tests {
         println!("iter df looks like ({})", df);
 
         //check ColInt
-        let a_ref_col = Box::new(column::ColInt { data: vec![1, 10] });
-        let a_act_col: &column::ColInt = downcast_any_to!(&df.data[0].data, column::ColInt);
-        assert_eq!(*a_act_col.data, *a_ref_col.data);
+        assert_eq!(df.column("a").unwrap().as_i32().unwrap(), &[1, 10]);
 
-        //check ColDoubleNullablet
-        let b_ref_col = Box::new(column::ColDoubleNullable {
-            data: vec![Some(2.2), Some(20.20)],
-        });
-        let b_act_col: &column::ColDoubleNullable =
-            downcast_any_to!(&df.data[1].data, column::ColDoubleNullable);
-        assert_eq!(*b_act_col.data, *b_ref_col.data);
+        //check ColDoubleNullable
+        let b_act_col: Vec<_> = df.column("b").unwrap().as_f32_opt().unwrap().collect();
+        assert_eq!(b_act_col, vec![Some(2.2), Some(20.20)]);
 
         //check ColString
-        let d_ref_col = Box::new(column::ColString {
-            data: vec![String::from("four"), String::from("fourty")],
-        });
-        let d_act_col: &column::ColString = downcast_any_to!(&df.data[3].data, column::ColString);
-        assert_eq!(*d_act_col.data, *d_ref_col.data);
+        let d_act_col: Vec<_> = df
+            .column("someothername")
+            .unwrap()
+            .as_str_iter()
+            .unwrap()
+            .collect();
+        assert_eq!(d_act_col, vec!["four", "fourty"]);
+    }
+
+    #[test]
+    fn append_to_frame_without_columns() {
+        let mut df = DataFrame::new("").unwrap();
+        assert!(df.append_str("").is_ok());
+        assert!(df.append_str("1,2").is_err());
+        assert_eq!(df.shape(), (0, 0));
+
+        let mut df = DataFrame::new("a:int").unwrap();
+        assert!(df.append_str("1\n2,3").is_err());
+        assert_eq!(df.shape(), (0, 1));
+
+        //rows before the one with too many cells are dropped again
+        let mut df = DataFrame::new("a:int,b:int").unwrap();
+        df.append_str("1,2").unwrap();
+        assert!(df.append_str("3,4\n5,6,7").is_err());
+        assert_eq!(df.shape(), (1, 2));
+        assert_eq!(df.data[1].data.to_string(), "2, ");
+
+        //as are those before a cell that does not parse, and the cells of a failing line
+        assert!(df.append_str("3,4\nx,6").is_err());
+        assert!(df.append_str("3,4\n5,x").is_err());
+        assert!(df.append_line("3,x").is_err());
+        assert_eq!(df.shape(), (1, 2));
+        assert!(df.data.iter().all(|c| c.data.len() == 1));
     }
 
     #[test]
@@ -330,6 +546,246 @@ mod tests {
         assert_eq!(test_err_string, ref_error.to_string());
     }
 
+    #[test]
+    fn test_csv_read_bool() {
+        let mycsvstr = "true,yes\n0,\nNo,maybe";
+        let myschema = "a:bool,b:boolNullable";
+        let df = csv_read_str_iter(mycsvstr, myschema).unwrap();
+
+        let a_act_col: &column::ColBool = df.data[0].get::<column::ColBool>().unwrap();
+        assert_eq!(
+            a_act_col.data.iter().collect::<Vec<_>>(),
+            vec![true, false, false]
+        );
+        assert_eq!(a_act_col.true_indices(), vec![0]);
+
+        let b_act_col: &column::ColBoolNullable =
+            df.data[1].get::<column::ColBoolNullable>().unwrap();
+        assert_eq!(b_act_col.get(0), Some(true));
+        assert_eq!(b_act_col.get(1), None);
+        assert_eq!(b_act_col.get(2), None);
+
+        //non-nullable bool rejects unknown tokens
+        let df = csv_read_str_iter("maybe", "a:bool");
+        assert!(df.is_err());
+
+        //tokens come from the type arguments, a side left out keeps its defaults
+        let myschema = "a:bool[true=Y|ja;false=N|nein],b:boolNullable[true=x]";
+        let df = csv_read_str_iter(
+            "y,x
+Nein,0
+ja,yes",
+            myschema,
+        )
+        .unwrap();
+        let a_act_col: &column::ColBool = df.data[0].get::<column::ColBool>().unwrap();
+        assert_eq!(
+            a_act_col.data.iter().collect::<Vec<_>>(),
+            vec![true, false, true]
+        );
+        assert_eq!(df.data[1].data.to_string(), "true, false, NA, ");
+        assert!(csv_read_str_iter("yes,x", myschema).is_err());
+
+        let df = DataFrame::new(myschema).unwrap();
+        assert_eq!(
+            df.schema().to_string(),
+            "a:bool[true=y|ja;false=n|nein],b:boolNullable[true=x]"
+        );
+        assert!(DataFrame::new("a:bool[maybe=m]").is_err());
+        assert!(DataFrame::new("a:bool[true=y;false=y]").is_err());
+    }
+
+    #[test]
+    fn test_csv_read_64bit() {
+        let mycsvstr = "3000000000,20.20,4000000000,\n-3000000000,0.1,1,7";
+        let myschema = "a:i64,b:f64,c:u32,d:u64Nullable";
+        let df = csv_read_str_iter(mycsvstr, myschema).unwrap();
+
+        let a_act_col: &column::ColI64 = df.data[0].get::<column::ColI64>().unwrap();
+        assert_eq!(a_act_col.data, vec![3000000000, -3000000000]);
+        let b_act_col: &column::ColF64 = df.data[1].get::<column::ColF64>().unwrap();
+        assert_eq!(b_act_col.data, vec![20.20, 0.1]);
+        let d_act_col: &column::ColU64Nullable =
+            df.data[3].get::<column::ColU64Nullable>().unwrap();
+        assert_eq!(d_act_col.iter().collect::<Vec<_>>(), vec![None, Some(7)]);
+
+        //int and double aliases can be configured to mean the 64-bit types
+        let df = DataFrame::new_with_width("a:int,b:doubleNullable", column::AliasWidth::Wide);
+        let dtypes: Vec<column::Dtype> = df.unwrap().data.iter().map(|x| x.dtype()).collect();
+        assert_eq!(
+            dtypes,
+            vec![column::Dtype::ColI64, column::Dtype::ColF64Nullable]
+        );
+    }
+
+    #[test]
+    fn test_csv_read_temporal() {
+        let mycsvstr = "04.03.2021,2021-03-04 05:06:07,1h30m\n,31/12/1999 23:59,";
+        let myschema =
+            "d:dateNullable[%d.%m.%Y],ts:datetime[%Y-%m-%d %H:%M:%S;unit=ms],dur:durationNullable";
+        let df = csv_read_str_iter(mycsvstr, myschema);
+        assert!(df.is_err(), "second ts does not match the format");
+
+        let mycsvstr = "04.03.2021,2021-03-04 05:06:07,1h30m\n,1999-12-31 23:59:00,";
+        let df = csv_read_str_iter(mycsvstr, myschema).unwrap();
+        assert_eq!(df.data[0].dtype(), column::Dtype::ColDateNullable);
+        assert_eq!(
+            df.data[1].dtype(),
+            column::Dtype::ColDatetime(column::TimeUnit::Milliseconds)
+        );
+
+        let d_act_col: &column::ColDateNullable =
+            df.data[0].get::<column::ColDateNullable>().unwrap();
+        assert_eq!(
+            d_act_col.iter().collect::<Vec<_>>(),
+            vec![Some(18690), None]
+        );
+
+        let ts_act_col: &column::ColDatetime = df.data[1].get::<column::ColDatetime>().unwrap();
+        assert_eq!(ts_act_col.data, vec![1614834367000, 946684740000]);
+
+        let dur_act_col: &column::ColDurationNullable =
+            df.data[2].get::<column::ColDurationNullable>().unwrap();
+        assert_eq!(
+            dur_act_col.iter().collect::<Vec<_>>(),
+            vec![Some(5_400_000_000), None]
+        );
+        assert_eq!(df.data[2].data.to_string(), "1h30m, NA, ");
+    }
+
+    #[test]
+    fn test_csv_read_categorical() {
+        let mycsvstr = "1,red\n2,blue\n3,red";
+        let df = csv_read_str_iter(mycsvstr, "a:int,colour:categorical").unwrap();
+
+        let c_act_col: &column::ColCategorical =
+            df.data[1].get::<column::ColCategorical>().unwrap();
+        assert_eq!(c_act_col.categories(), vec!["red", "blue"]);
+        assert_eq!(c_act_col.codes(), &[0, 1, 0]);
+    }
+
+    #[test]
+    fn test_csv_read_decimal() {
+        let mycsvstr = "1,20.20\n2,0.05\n3,";
+        let myschema = "id:int,amount:decimalNullable[18,4]";
+        let df = csv_read_str_iter(mycsvstr, myschema).unwrap();
+        assert_eq!(df.shape(), (3, 2));
+
+        let b_act_col: &column::ColDecimalNullable =
+            df.data[1].get::<column::ColDecimalNullable>().unwrap();
+        assert_eq!(b_act_col.data, vec![202000, 500, 0]);
+        assert_eq!(b_act_col.validity.to_string(), "110");
+        assert_eq!(b_act_col.sum().unwrap().to_string(), "20.2500");
+
+        assert!(DataFrame::new("amount:decimal[4,18]").is_err());
+    }
+
+    #[test]
+    fn test_csv_read_binary() {
+        let mycsvstr = "1,0a0b,aGk=\n2,,";
+        let myschema = "id:int,digest:binary,payload:binaryNullable[base64]";
+        let df = csv_read_str_iter(mycsvstr, myschema).unwrap();
+
+        let b_act_col: &column::ColBinary = df.data[1].get::<column::ColBinary>().unwrap();
+        assert_eq!(b_act_col.get(0), &[10, 11]);
+        assert_eq!(df.data[2].data.to_string(), "aGk=, NA, ");
+
+        //the encoding survives serialization
+        let serialized = serde_json::to_string(&df).unwrap();
+        let df2: DataFrame = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(df2.to_string(), df.to_string());
+        assert!(DataFrame::new("a:binary[base32]").is_err());
+    }
+
+    #[test]
+    fn test_csv_read_nulls() {
+        let mycsvstr = "1,,x\n,2.5,\n3,,z";
+        let myschema = "a:intNullable,b:f64Nullable,c:stringPoolNullable";
+        let df = csv_read_str_iter(mycsvstr, myschema).unwrap();
+
+        let null_counts: Vec<usize> = df.data.iter().map(|c| c.data.null_count()).collect();
+        assert_eq!(null_counts, vec![1, 2, 1]);
+        assert!(df.data[2].data.is_null(1) && !df.data[2].data.is_null(2));
+        assert_eq!(df.data[2].data.to_string(), "x, NA, z, ");
+
+        let a_act_col: &column::ColIntNullable =
+            df.data[0].get::<column::ColIntNullable>().unwrap();
+        assert_eq!(a_act_col.data, vec![1, 0, 3]);
+        assert_eq!(df.data[0].data.validity().unwrap().to_string(), "101");
+
+        //non-nullable columns have no validity
+        let df = csv_read_str_iter("1,x", "a:int,b:string").unwrap();
+        assert!(df.data[1].data.validity().is_none());
+        assert_eq!(
+            df.data[1].dtype().nullable(),
+            column::Dtype::ColStringNullable
+        );
+        assert_eq!(df.data[1].data.null_count(), 0);
+    }
+
+    #[test]
+    fn typed_column_access() {
+        let df = csv_read_str_iter("1,2.5,x\n2,,y", "a:i64,b:f64Nullable,c:stringpool").unwrap();
+        assert_eq!(df.column("a").unwrap().as_i64().unwrap(), &[1, 2]);
+        let a_opt: Vec<_> = df.column("a").unwrap().as_i64_opt().unwrap().collect();
+        assert_eq!(a_opt, vec![Some(1), Some(2)]);
+        let b_opt: Vec<_> = df.column("b").unwrap().as_f64_opt().unwrap().collect();
+        assert_eq!(b_opt, vec![Some(2.5), None]);
+        let c: Vec<_> = df.column("c").unwrap().as_str_iter().unwrap().collect();
+        assert_eq!(c, vec!["x", "y"]);
+
+        //mismatches name the column and both types
+        let err = df.column("b").unwrap().as_f64().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "ColError: 'DtypeMismatch' \"column \"b\" is DType: ColF64Nullable, not ColF64\""
+        );
+        assert!(df.column("a").unwrap().as_i32().is_err());
+        assert!(df.column("a").unwrap().as_str_iter().is_err());
+        assert!(df.column("nope").is_err());
+    }
+
+    #[test]
+    fn select_drop_rename() {
+        let mut df = csv_read_str_iter("1,2.5,x\n2,,y", "a:int,b:doubleNullable,c:string").unwrap();
+        let sel = df.select(&["c", "a"]).unwrap();
+        assert_eq!(sel.names(), vec![Some("c"), Some("a")]);
+        assert_eq!(sel.column("a").unwrap().as_i32().unwrap(), &[1, 2]);
+        assert!(df.select(&["a", "a"]).is_err());
+        assert!(df.select(&["nope"]).is_err());
+
+        df.rename("b", "d").unwrap();
+        assert!(df.column("b").is_err());
+        assert!(df.rename("d", "a").is_err());
+        assert!(df.drop(&["a", "nope"]).is_err());
+        df.drop(&["a"]).unwrap();
+        assert_eq!(df.names(), vec![Some("d"), Some("c")]);
+        assert_eq!(df.column("c").unwrap().data.to_string(), "x, y, ");
+
+        let mut e = column::Column::new(Some(String::from("e")), column::Dtype::ColInt);
+        e.push_from_str("7").unwrap();
+        assert!(df.with_column(e.clone()).is_err());
+        e.push_from_str("8").unwrap();
+        df.with_column(e).unwrap();
+        let mut c = column::Column::new(Some(String::from("c")), column::Dtype::ColI64);
+        c.push_from_str("3").unwrap();
+        c.push_from_str("4").unwrap();
+        df.with_column(c).unwrap();
+        assert_eq!(df.names(), vec![Some("d"), Some("c"), Some("e")]);
+        assert_eq!(df.column("c").unwrap().as_i64().unwrap(), &[3, 4]);
+
+        df.column_mut("e").unwrap().name = Some(String::from("f"));
+        assert_eq!(df.column("f").unwrap().as_i32().unwrap(), &[7, 8]);
+        //direct changes to data are still found
+        df.data.swap(0, 2);
+        assert_eq!(
+            df.column("d").unwrap().dtype(),
+            column::Dtype::ColDoubleNullable
+        );
+
+        assert!(DataFrame::new("a:int,a:string").is_err());
+    }
+
     #[test]
     fn return_error_schema_errors() {
         //col c is int and must fail
@@ -341,9 +797,15 @@ mod tests {
         //df.unwrap();
 
         let expected_error_msg = "dataframe error failed to parse schema
-sub error 0: ColError: 'ParseDataType', do not recognize  \"not_a_col_type\" as datatype
-sub error 1: ColError: 'SchemaSyntax' \"unexpected third token \"3rd_token\" for column descr No. 1\"
-sub error 2: ColError: 'ParseDataType', do not recognize  \"\" as datatype".to_string();
+sub error 0: SchemaError: do not recognize \"not_a_col_type\" as datatype at line 1, column 3
+  a:not_a_col_type,3rd_token:b:int,,correct_col:string
+    ^
+sub error 1: SchemaError: unexpected ':' after the dtype, quote names that contain ':' at line 1, column 29
+  a:not_a_col_type,3rd_token:b:int,,correct_col:string
+                              ^
+sub error 2: SchemaError: empty column descriptor at line 1, column 34
+  a:not_a_col_type,3rd_token:b:int,,correct_col:string
+                                   ^".to_string();
 
         if let Err(err) = df {
             let err_text = err.to_string();
@@ -0,0 +1,539 @@
+use super::column::{
+    extend_source, type_descr, AliasWidth, CastOptions, ColError, ColErrorcode, ColString, Column,
+    Dtype, Value, VectorData,
+};
+use super::{split_outside_brackets, DFError, DataFrame, Result};
+use crate::bitmap::Bitmap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::any::Any;
+
+//lists of any element type, row i holds child elements offsets[i]..offsets[i + 1].
+//listNullable has a validity bitmap, its null rows hold no elements
+#[derive(Serialize, Deserialize)]
+pub struct ColList {
+    pub offsets: Vec<usize>,
+    pub child: Box<dyn VectorData>,
+    #[serde(default)]
+    pub validity: Option<Bitmap>,
+}
+
+//struct rows, one named child column per field, all of equal length. structNullable has
+//a validity bitmap and nullable fields, which are null in null rows
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct ColStruct {
+    pub fields: Vec<Column>,
+    #[serde(default)]
+    pub validity: Option<Bitmap>,
+}
+
+impl Default for ColList {
+    fn default() -> ColList {
+        ColList::new(Box::new(ColString::default()))
+    }
+}
+
+impl Clone for ColList {
+    fn clone(&self) -> Self {
+        ColList {
+            offsets: self.offsets.clone(),
+            child: self.child.boxed_clone(),
+            validity: self.validity.clone(),
+        }
+    }
+}
+
+//a CSV cell may still carry its quotes, "[""a""]" is the JSON ["a"]
+fn unquote_csv(x: &str) -> String {
+    let x = x.trim();
+    if x.len() >= 2 && x.starts_with('"') && x.ends_with('"') {
+        x[1..x.len() - 1].replace("\"\"", "\"")
+    } else {
+        x.to_string()
+    }
+}
+
+fn parse_json(x: &str, what: &str) -> Result<JsonValue> {
+    serde_json::from_str(&unquote_csv(x)).map_err(|e| {
+        Box::new(ColError {
+            errorcode: ColErrorcode::ParseValue,
+            error_msg: format!("cannot parse \"{}\" as {}: {}", x, what, e),
+        }) as Box<dyn std::error::Error>
+    })
+}
+
+//push one json value into any column, scalars go through the usual text parsing
+fn push_json(data: &mut dyn VectorData, value: &JsonValue) -> Result<()> {
+    match value {
+        JsonValue::Null => data.push_from_str(""),
+        JsonValue::String(s) => data.push_from_str(s),
+        JsonValue::Bool(_) | JsonValue::Number(_) => data.push_from_str(&value.to_string()),
+        JsonValue::Array(_) | JsonValue::Object(_) => data.push_from_str(&value.to_string()),
+    }
+}
+
+//cell value as json, values without a json counterpart are written as their text
+pub fn value_to_json(value: Value) -> JsonValue {
+    match value {
+        Value::Null => JsonValue::Null,
+        Value::Int(x) => JsonValue::from(x),
+        Value::UInt(x) => JsonValue::from(x),
+        Value::Double(x) => JsonValue::from(x),
+        Value::Bool(x) => JsonValue::from(x),
+        Value::Str(x) => JsonValue::from(x),
+        Value::List(child, start, end) => JsonValue::Array(
+            (start..end)
+                .map(|i| value_to_json(child.get_value(i)))
+                .collect(),
+        ),
+        Value::Struct(fields, idx) => JsonValue::Object(
+            fields
+                .iter()
+                .enumerate()
+                .map(|(i, f)| (field_name(f, i), value_to_json(f.data.get_value(idx))))
+                .collect(),
+        ),
+        other => JsonValue::from(other.to_string()),
+    }
+}
+
+fn field_name(field: &Column, i: usize) -> String {
+    field.name.clone().unwrap_or_else(|| format!("field_{}", i))
+}
+
+fn all_valid(len: usize) -> Option<Bitmap> {
+    Some((0..len).map(|_| true).collect())
+}
+
+//null rows of nullable columns, an empty cell or a json null
+fn is_null_cell(validity: &Option<Bitmap>, x: &str) -> bool {
+    validity.is_some() && matches!(x.trim(), "" | "null")
+}
+
+impl ColList {
+    pub fn new(child: Box<dyn VectorData>) -> ColList {
+        ColList {
+            offsets: vec![0],
+            child,
+            validity: None,
+        }
+    }
+
+    //as self, but able to hold nulls
+    pub fn with_nulls(mut self) -> ColList {
+        if self.validity.is_none() {
+            self.validity = all_valid(self.len());
+        }
+        self
+    }
+
+    //args is the element dtype descriptor, e.g. int for list[int]
+    pub fn from_args(args: &str, width: AliasWidth) -> Result<ColList> {
+        let child = Column::from_descr(None, args, width)?;
+        Ok(ColList::new(child.data))
+    }
+
+    pub fn list_len(&self, idx: usize) -> usize {
+        self.offsets[idx + 1] - self.offsets[idx]
+    }
+}
+
+impl ColStruct {
+    //args are the fields as in a schema, e.g. x:double,y:double for struct[x:double,y:double]
+    pub fn from_args(args: &str, width: AliasWidth) -> Result<ColStruct> {
+        let mut fields = Vec::new();
+        for field_descr in split_outside_brackets(args, &[',']) {
+            let tokens = split_outside_brackets(field_descr, &[':']);
+            if tokens.len() != 2 {
+                Err(ColError {
+                    errorcode: ColErrorcode::SchemaSyntax,
+                    error_msg: format!("struct field \"{}\" is not name:type", field_descr),
+                })?;
+            }
+            let name = tokens[0].trim().to_string();
+            fields.push(Column::from_descr(Some(name), tokens[1].trim(), width)?);
+        }
+        Ok(ColStruct {
+            fields,
+            validity: None,
+        })
+    }
+
+    //as self, but able to hold nulls. the fields are cast to their nullable dtypes
+    pub fn with_nulls(mut self) -> Result<ColStruct> {
+        if self.validity.is_none() {
+            self.validity = all_valid(self.len());
+            for field in self.fields.iter_mut() {
+                *field = nullable_field(field)?;
+            }
+        }
+        Ok(self)
+    }
+}
+
+//empty fields are rebuilt from their descriptor, which keeps arguments like the element
+//dtype of lists that a cast would lose
+fn nullable_field(field: &Column) -> Result<Column> {
+    if field.dtype().is_nullable() {
+        Ok(field.clone())
+    } else if field.data.len() == 0 {
+        let descr = format!("{}?", field.type_descr());
+        Column::from_descr(field.name.clone(), &descr, AliasWidth::default())
+    } else {
+        field.cast(field.dtype().nullable(), CastOptions::strict())
+    }
+}
+
+#[typetag::serde]
+impl VectorData for ColList {
+    //a json array, or an empty cell for an empty list. empty cells and json null are null
+    //in listNullable
+    fn push_from_str(&mut self, x: &str) -> Result<()> {
+        let null = is_null_cell(&self.validity, x);
+        if !null && !x.trim().is_empty() {
+            match parse_json(x, "list")? {
+                //elements pushed before a failing one are removed again
+                JsonValue::Array(values) => {
+                    let pushed = values
+                        .iter()
+                        .try_for_each(|v| push_json(self.child.as_mut(), v));
+                    if let Err(e) = pushed {
+                        self.child.truncate(*self.offsets.last().unwrap());
+                        return Err(e);
+                    }
+                }
+                JsonValue::Null => (),
+                _ => Err(ColError {
+                    errorcode: ColErrorcode::ParseValue,
+                    error_msg: format!("\"{}\" is not a json array", x),
+                })?,
+            }
+        }
+        self.offsets.push(self.child.len());
+        if let Some(v) = self.validity.as_mut() {
+            v.push(!null);
+        }
+        Ok(())
+    }
+    fn to_string(&self) -> String {
+        (0..self.len())
+            .map(|i| self.get_value(i).to_string())
+            .fold(String::new(), |a, b| a + &b[..] + ", ")
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn reserve(&mut self, additional: usize) {
+        self.offsets.reserve(additional);
+        if let Some(v) = self.validity.as_mut() {
+            v.reserve(additional);
+        }
+    }
+    fn truncate(&mut self, len: usize) {
+        self.offsets.truncate(len + 1);
+        self.child.truncate(*self.offsets.last().unwrap());
+        if let Some(v) = self.validity.as_mut() {
+            v.truncate(len);
+        }
+    }
+    fn boxed_clone(&self) -> Box<dyn VectorData> {
+        Box::new(self.clone())
+    }
+    fn dtype(&self) -> Dtype {
+        match self.validity {
+            Some(_) => Dtype::ColListNullable,
+            None => Dtype::ColList,
+        }
+    }
+    fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+    fn get_value(&self, idx: usize) -> Value<'_> {
+        if self.is_null(idx) {
+            return Value::Null;
+        }
+        Value::List(
+            self.child.as_ref(),
+            self.offsets[idx],
+            self.offsets[idx + 1],
+        )
+    }
+    fn take(&self, indices: &[usize]) -> Box<dyn VectorData> {
+        let mut offsets = vec![0];
+        let mut child_indices = Vec::new();
+        for i in indices.iter() {
+            child_indices.extend(self.offsets[*i]..self.offsets[*i + 1]);
+            offsets.push(child_indices.len());
+        }
+        Box::new(ColList {
+            offsets,
+            child: self.child.take(&child_indices),
+            validity: self.validity.as_ref().map(|v| v.take(indices)),
+        })
+    }
+    fn validity(&self) -> Option<&Bitmap> {
+        self.validity.as_ref()
+    }
+    fn type_args(&self) -> String {
+        type_descr(self.child.as_ref())
+    }
+    //the children have to be of the same dtype too
+    fn extend(&mut self, other: &dyn VectorData) -> Result<()> {
+        let other: &Self = extend_source(other, self.dtype())?;
+        let base = self.child.len();
+        self.child.extend(other.child.as_ref())?;
+        self.offsets
+            .extend(other.offsets[1..].iter().map(|o| base + o));
+        if let (Some(v), Some(o)) = (self.validity.as_mut(), other.validity.as_ref()) {
+            v.extend(o);
+        }
+        Ok(())
+    }
+}
+
+#[typetag::serde]
+impl VectorData for ColStruct {
+    //a json object, missing fields are pushed as empty cells. empty cells and json null are
+    //null in structNullable
+    fn push_from_str(&mut self, x: &str) -> Result<()> {
+        let null = is_null_cell(&self.validity, x);
+        let value = if null || x.trim().is_empty() {
+            JsonValue::Null
+        } else {
+            parse_json(x, "struct")?
+        };
+        let object = match value {
+            JsonValue::Object(o) => o,
+            JsonValue::Null => serde_json::Map::new(),
+            _ => Err(ColError {
+                errorcode: ColErrorcode::ParseValue,
+                error_msg: format!("\"{}\" is not a json object", x),
+            })?,
+        };
+        //fields pushed before a failing one are truncated again
+        let len = self.len();
+        let pushed = self
+            .fields
+            .iter_mut()
+            .enumerate()
+            .try_for_each(|(i, field)| {
+                let v = object
+                    .get(&field_name(field, i))
+                    .unwrap_or(&JsonValue::Null);
+                push_json(field.data.as_mut(), v)
+            });
+        if let Err(e) = pushed {
+            self.fields.iter_mut().for_each(|f| f.data.truncate(len));
+            return Err(e);
+        }
+        if let Some(v) = self.validity.as_mut() {
+            v.push(!null);
+        }
+        Ok(())
+    }
+    fn to_string(&self) -> String {
+        (0..self.len())
+            .map(|i| self.get_value(i).to_string())
+            .fold(String::new(), |a, b| a + &b[..] + ", ")
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn reserve(&mut self, additional: usize) {
+        self.fields
+            .iter_mut()
+            .for_each(|f| f.data.reserve(additional));
+        if let Some(v) = self.validity.as_mut() {
+            v.reserve(additional);
+        }
+    }
+    fn truncate(&mut self, len: usize) {
+        self.fields.iter_mut().for_each(|f| f.data.truncate(len));
+        if let Some(v) = self.validity.as_mut() {
+            v.truncate(len);
+        }
+    }
+    fn boxed_clone(&self) -> Box<dyn VectorData> {
+        Box::new(self.clone())
+    }
+    fn dtype(&self) -> Dtype {
+        match self.validity {
+            Some(_) => Dtype::ColStructNullable,
+            None => Dtype::ColStruct,
+        }
+    }
+    fn len(&self) -> usize {
+        self.fields.first().map_or(0, |f| f.data.len())
+    }
+    fn get_value(&self, idx: usize) -> Value<'_> {
+        if self.is_null(idx) {
+            return Value::Null;
+        }
+        Value::Struct(&self.fields, idx)
+    }
+    fn take(&self, indices: &[usize]) -> Box<dyn VectorData> {
+        Box::new(ColStruct {
+            fields: self.fields.iter().map(|f| f.take(indices)).collect(),
+            validity: self.validity.as_ref().map(|v| v.take(indices)),
+        })
+    }
+    fn validity(&self) -> Option<&Bitmap> {
+        self.validity.as_ref()
+    }
+    fn type_args(&self) -> String {
+        let fields: Vec<String> = self
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, f)| format!("{}:{}", field_name(f, i), f.type_descr()))
+            .collect();
+        fields.join(",")
+    }
+    //fields are matched by position and have to agree in name and dtype
+    fn extend(&mut self, other: &dyn VectorData) -> Result<()> {
+        let other: &Self = extend_source(other, self.dtype())?;
+        let same_names = self.fields.len() == other.fields.len()
+            && self
+                .fields
+                .iter()
+                .zip(other.fields.iter())
+                .all(|(a, b)| a.name == b.name);
+        if !same_names {
+            Err(ColError {
+                errorcode: ColErrorcode::DtypeMismatch,
+                error_msg: String::from("cannot extend a struct by one with other fields"),
+            })?;
+        }
+        for (field, other_field) in self.fields.iter_mut().zip(other.fields.iter()) {
+            field.data.extend(other_field.data.as_ref())?;
+        }
+        if let (Some(v), Some(o)) = (self.validity.as_mut(), other.validity.as_ref()) {
+            v.extend(o);
+        }
+        Ok(())
+    }
+}
+
+impl DataFrame {
+    //one row per list element, other columns are repeated, rows with empty lists are dropped
+    pub fn explode(&self, column: &str) -> Result<DataFrame> {
+        let i_col = self.position_of(column)?;
+        let list = self.data[i_col].get::<ColList>()?;
+
+        let mut row_indices = Vec::with_capacity(list.child.len());
+        let mut child_indices = Vec::with_capacity(list.child.len());
+        for i_row in 0..list.len() {
+            row_indices.extend(std::iter::repeat_n(i_row, list.list_len(i_row)));
+            child_indices.extend(list.offsets[i_row]..list.offsets[i_row + 1]);
+        }
+
+        let data = self
+            .data
+            .iter()
+            .enumerate()
+            .map(|(i, col)| {
+                if i == i_col {
+                    col.with_data(list.child.take(&child_indices))
+                } else {
+                    col.take(&row_indices)
+                }
+            })
+            .collect();
+        DataFrame::from_columns(data)
+    }
+
+    //replace a struct column by its fields as top-level columns
+    pub fn unnest(&self, column: &str) -> Result<DataFrame> {
+        let i_col = self.position_of(column)?;
+        let strct = self.data[i_col].get::<ColStruct>()?;
+
+        let mut data: Vec<Column> = self.data[..i_col].to_vec();
+        data.extend(strct.fields.iter().cloned());
+        data.extend(self.data[i_col + 1..].iter().cloned());
+
+        let mut names: Vec<&str> = data.iter().filter_map(|c| c.name.as_deref()).collect();
+        names.sort_unstable();
+        if let Some(w) = names.windows(2).find(|w| w[0] == w[1]) {
+            Err(DFError {
+                error_msg: format!("unnest of \"{}\" duplicates column \"{}\"", column, w[0]),
+                sub_errors: Vec::new(),
+            })?;
+        }
+        DataFrame::from_columns(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataframe::{column, csv_read_str_iter};
+
+    #[test]
+    fn list_and_struct_from_csv() {
+        let mycsvstr = "1,\"[10,20]\",\"{\"\"x\"\":1.5,\"\"tag\"\":\"\"a\"\"}\"\n2,[],\n3,[30],{}";
+        let myschema = "id:int,v:list[int],p:struct[x:doubleNullable,tag:string]";
+        let df = csv_read_str_iter(mycsvstr, myschema).unwrap();
+        assert_eq!(df.shape(), (3, 3));
+        assert_eq!(df.data[1].data.to_string(), "[10, 20], [], [30], ");
+        assert_eq!(df.data[2].data.get_value(0).to_string(), "{x: 1.5, tag: a}");
+
+        let json = value_to_json(df.data[1].data.get_value(0));
+        assert_eq!(json.to_string(), "[10,20]");
+    }
+
+    #[test]
+    fn explode_list() {
+        let df = csv_read_str_iter("1,\"[10,20]\"\n2,[]\n3,[30]", "id:int,v:list[int]").unwrap();
+        let exploded = df.explode("v").unwrap();
+        assert_eq!(exploded.shape(), (3, 2));
+
+        let ids = exploded.data[0].get::<column::ColInt>().unwrap();
+        assert_eq!(ids.data, vec![1, 1, 3]);
+        assert_eq!(exploded.data[1].data.to_string(), "10, 20, 30, ");
+        assert!(df.explode("id").is_err());
+
+        //extending shifts the offsets of the appended lists
+        let mut v = df.data[1].data.boxed_clone();
+        v.extend(df.data[1].data.as_ref()).unwrap();
+        assert_eq!(v.len(), 6);
+        assert_eq!(v.get_value(5).to_string(), "[30]");
+        assert!(v.extend(df.data[0].data.as_ref()).is_err());
+
+        //metadata is kept, and a failing element leaves no elements behind
+        let mut df = df;
+        df.data[0].metadata.insert("unit".into(), "n".into());
+        df.data[1].metadata.insert("unit".into(), "kg".into());
+        let exploded = df.explode("v").unwrap();
+        assert_eq!(exploded.data[0].metadata["unit"], "n");
+        assert_eq!(exploded.data[1].metadata["unit"], "kg");
+        assert!(df.data[1].data.push_from_str("[40,\"x\"]").is_err());
+        df.data[1].data.push_from_str("[50]").unwrap();
+        assert_eq!(df.data[1].data.get_value(3).to_string(), "[50]");
+    }
+
+    #[test]
+    fn unnest_struct() {
+        let mycsvstr = "1,\"{\"\"x\"\":1.5,\"\"y\"\":2}\"\n2,{}";
+        let df = csv_read_str_iter(mycsvstr, "id:int,p:struct[x:doubleNullable,y:int]");
+        assert!(df.is_err(), "missing y is not nullable");
+
+        let df =
+            csv_read_str_iter(mycsvstr, "id:int,p:struct[x:doubleNullable,y:intNullable]").unwrap();
+        let flat = df.unnest("p").unwrap();
+        let names: Vec<_> = flat.data.iter().map(|c| c.name.clone().unwrap()).collect();
+        assert_eq!(names, vec!["id", "x", "y"]);
+        assert_eq!(flat.data[1].data.to_string(), "1.5, NA, ");
+
+        //a failing field leaves the fields before it unchanged
+        let df =
+            csv_read_str_iter("\"{\"\"x\"\":1,\"\"y\"\":2}\"", "p:struct[x:int,y:int]").unwrap();
+        let mut p = df.data[0].data.boxed_clone();
+        assert!(p.push_from_str("{\"x\":3,\"y\":\"z\"}").is_err());
+        assert_eq!(p.len(), 1);
+        p.push_from_str("{\"x\":5,\"y\":4}").unwrap();
+        assert_eq!(p.get_value(1).to_string(), "{x: 5, y: 4}");
+
+        let clash = csv_read_str_iter("1,{}", "x:int,p:struct[x:intNullable]").unwrap();
+        assert!(clash.unnest("p").is_err());
+    }
+}
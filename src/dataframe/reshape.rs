@@ -0,0 +1,196 @@
+use super::column::{CastOptions, Column, Dtype, Value, VectorData};
+use super::concat::stack_columns;
+use super::groupby::{group_rows, Agg};
+use super::join::take_opt;
+use super::{DFError, DataFrame, Result};
+use std::collections::HashSet;
+
+fn reshape_err(error_msg: String) -> Box<dyn std::error::Error> {
+    Box::new(DFError {
+        error_msg,
+        sub_errors: Vec::new(),
+    })
+}
+
+impl DataFrame {
+    //wide to long: the id columns repeated for each value column, a string column variable
+    //with the name of the value column and a column value with its cells, upcast to a common
+    //dtype. no value_vars melts all columns that are not ids
+    pub fn melt(&self, id_vars: &[&str], value_vars: &[&str]) -> Result<DataFrame> {
+        let ids = id_vars
+            .iter()
+            .map(|name| self.column(name))
+            .collect::<Result<Vec<&Column>>>()?;
+        let values: Vec<&Column> = if value_vars.is_empty() {
+            self.data
+                .iter()
+                .filter(|c| !c.name.as_deref().is_some_and(|n| id_vars.contains(&n)))
+                .collect()
+        } else {
+            value_vars
+                .iter()
+                .map(|name| self.column(name))
+                .collect::<Result<Vec<&Column>>>()?
+        };
+        if values.is_empty() {
+            return Err(reshape_err(String::from("melt has no value columns")));
+        }
+
+        let nrow = self.shape().0;
+        let rows: Vec<usize> = (0..values.len()).flat_map(|_| 0..nrow).collect();
+        let mut data: Vec<Column> = ids.iter().map(|c| c.take(&rows)).collect();
+
+        let mut variable = Column::new(Some(String::from("variable")), Dtype::ColString);
+        variable.data.reserve(rows.len());
+        for col in values.iter() {
+            let name = col.name.as_deref().unwrap_or("None");
+            (0..nrow).try_for_each(|_| variable.data.push_from_str(name))?;
+        }
+        data.push(variable);
+
+        let mut value = values[0].take(&[]);
+        for col in values.iter() {
+            value = stack_columns(value, col).map_err(|e| {
+                reshape_err(format!(
+                    "cannot melt {} and {}: {}",
+                    values[0].dtype(),
+                    col.dtype(),
+                    e
+                ))
+            })?;
+        }
+        value.name = Some(String::from("value"));
+        value.metadata.clear();
+        value.constraints.clear();
+        data.push(value);
+        DataFrame::from_columns(data)
+    }
+
+    //long to wide: one row per index key and one column per distinct cell of columns,
+    //named by the cell, null cells by "null", holding the agg of values. the value columns
+    //are nullable, combinations without rows are null. cells named like an index column or
+    //like another cell, e.g. the string "null" and a null, are an error
+    pub fn pivot(
+        &self,
+        index: &[&str],
+        columns: &str,
+        values: &str,
+        agg: Agg,
+    ) -> Result<DataFrame> {
+        let mut keys = index.to_vec();
+        keys.push(columns);
+        let long = self.group_by(&keys)?.agg(&[(values, agg)])?;
+        let (nlong, nindex) = (long.shape().0, index.len());
+
+        let index_cols: Vec<&dyn VectorData> = long.data[..nindex]
+            .iter()
+            .map(|c| c.data.as_ref())
+            .collect();
+        let pivot_col = long.data[nindex].data.as_ref();
+        let row_groups = group_rows(&index_cols, nlong);
+        let col_groups = group_rows(&[pivot_col], nlong);
+        let mut row_of = vec![0; nlong];
+        for (g, rows) in row_groups.iter().enumerate() {
+            rows.iter().for_each(|&r| row_of[r] = g);
+        }
+
+        let mut names: HashSet<String> = index.iter().map(|n| n.to_string()).collect();
+        let first: Vec<usize> = row_groups.iter().map(|g| g[0]).collect();
+        let mut data: Vec<Column> = long.data[..nindex].iter().map(|c| c.take(&first)).collect();
+        let agg_col = &long.data[nindex + 1];
+        let agg_col = agg_col.cast(agg_col.dtype().nullable(), CastOptions::strict())?;
+        for rows in col_groups.iter() {
+            let name = match pivot_col.get_value(rows[0]) {
+                Value::Null => String::from("null"),
+                value => value.to_string(),
+            };
+            if !names.insert(name.clone()) {
+                return Err(reshape_err(format!(
+                    "pivot column name \"{}\" of column \"{}\" is not unique",
+                    name, columns
+                )));
+            }
+            let mut cells: Vec<Option<usize>> = vec![None; row_groups.len()];
+            rows.iter().for_each(|&r| cells[row_of[r]] = Some(r));
+            let mut out = take_opt(&agg_col, &cells)?;
+            out.name = Some(name);
+            data.push(out);
+        }
+        DataFrame::from_columns(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataframe::csv_read_str_iter;
+
+    #[test]
+    fn melt_and_pivot() {
+        let wide = csv_read_str_iter("a,1,2.5\nb,3,", "id:string,x:int,y:doubleNullable").unwrap();
+        let long = wide.melt(&["id"], &[]).unwrap();
+        assert_eq!(
+            long.names(),
+            vec![Some("id"), Some("variable"), Some("value")]
+        );
+        assert_eq!(long.column("id").unwrap().data.to_string(), "a, b, a, b, ");
+        assert_eq!(
+            long.column("variable").unwrap().data.to_string(),
+            "x, x, y, y, "
+        );
+        assert_eq!(long.column("value").unwrap().dtype(), Dtype::ColF64Nullable);
+        assert_eq!(
+            long.column("value").unwrap().data.to_string(),
+            "1, 3, 2.5, NA, "
+        );
+        assert_eq!(wide.melt(&["id"], &["x"]).unwrap().shape(), (2, 3));
+        assert!(wide.melt(&["id"], &["id", "nope"]).is_err());
+        assert!(wide.melt(&[], &["id", "x"]).is_err());
+
+        let sales = csv_read_str_iter(
+            "de,jan,10\nde,feb,20\nfr,jan,5\nde,jan,1",
+            "country:stringPool,month:string,n:int",
+        )
+        .unwrap();
+        let df = sales.pivot(&["country"], "month", "n", Agg::Sum).unwrap();
+        assert_eq!(df.names(), vec![Some("country"), Some("jan"), Some("feb")]);
+        assert_eq!(df.column("jan").unwrap().dtype(), Dtype::ColI64Nullable);
+        assert_eq!(df.column("jan").unwrap().data.to_string(), "11, 5, ");
+        assert_eq!(df.column("feb").unwrap().dtype(), Dtype::ColI64Nullable);
+        assert_eq!(df.column("feb").unwrap().data.to_string(), "20, NA, ");
+
+        let df = sales.pivot(&[], "country", "n", Agg::First).unwrap();
+        assert_eq!(df.names(), vec![Some("de"), Some("fr")]);
+        assert_eq!(df.column("de").unwrap().data.to_string(), "10, ");
+        assert!(sales
+            .pivot(&["country"], "month", "month", Agg::Mean)
+            .is_err());
+    }
+
+    #[test]
+    fn pivot_null_and_colliding_names() {
+        let long = csv_read_str_iter(
+            "a,x,1\na,,2\nb,y,3\nb,,4",
+            "id:string,key:stringNullable,n:int",
+        )
+        .unwrap();
+        let df = long.pivot(&["id"], "key", "n", Agg::Max).unwrap();
+        assert_eq!(
+            df.names(),
+            vec![Some("id"), Some("x"), Some("null"), Some("y")]
+        );
+        assert_eq!(df.column("null").unwrap().dtype(), Dtype::ColIntNullable);
+        assert_eq!(df.column("null").unwrap().data.to_string(), "2, 4, ");
+        assert_eq!(df.column("x").unwrap().data.to_string(), "1, NA, ");
+
+        let mut clash = long.clone();
+        clash.append_line("a,null,5").unwrap();
+        let err = clash.pivot(&["id"], "key", "n", Agg::Max).err().unwrap();
+        assert!(err
+            .to_string()
+            .contains("\"null\" of column \"key\" is not unique"));
+        clash = long.clone();
+        clash.append_line("b,id,5").unwrap();
+        assert!(clash.pivot(&["id"], "key", "n", Agg::Max).is_err());
+    }
+}
@@ -0,0 +1,812 @@
+use super::column::{AliasWidth, CellOptions, ColError, ColErrorcode, Column, Dtype};
+use super::{split_outside_brackets, DFError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+//SchemaError points at the character of the schema string that does not parse
+#[derive(Debug, Clone)]
+pub struct SchemaError {
+    pub line: usize,   //1-based
+    pub column: usize, //1-based, counted in chars
+    pub error_msg: String,
+    line_text: String,
+}
+
+impl SchemaError {
+    fn new(src: &str, pos: usize, error_msg: &str) -> SchemaError {
+        let line_start = src[..pos].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = src[pos..].find('\n').map_or(src.len(), |i| pos + i);
+        SchemaError {
+            line: src[..pos].matches('\n').count() + 1,
+            column: src[line_start..pos].chars().count() + 1,
+            error_msg: error_msg.to_string(),
+            line_text: src[line_start..line_end].trim_end().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "SchemaError: {} at line {}, column {}\n  {}\n  {}^",
+            self.error_msg,
+            self.line,
+            self.column,
+            self.line_text,
+            " ".repeat(self.column - 1)
+        )
+    }
+}
+impl error::Error for SchemaError {}
+
+//Field is one column of a schema, args are the bracket arguments of the dtype, e.g. the
+//datetime format, metadata holds free-form key/values like units or descriptions
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Field {
+    pub name: Option<String>,
+    pub dtype: Dtype,
+    #[serde(default)]
+    pub args: String,
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+    #[serde(default)]
+    pub options: CellOptions,
+}
+
+impl Field {
+    //args default to what the dtype implies, e.g. unit=ms for ColDatetime(Milliseconds)
+    pub fn new(name: Option<String>, dtype: Dtype) -> Field {
+        Field {
+            name,
+            dtype,
+            args: Column::new(None, dtype).data.type_args(),
+            metadata: BTreeMap::new(),
+            options: CellOptions::default(),
+        }
+    }
+
+    pub fn with_args(mut self, args: &str) -> Field {
+        self.args = args.to_string();
+        self
+    }
+
+    pub fn with_options(mut self, options: CellOptions) -> Field {
+        self.options = options;
+        self
+    }
+
+    pub fn with_metadata(mut self, key: &str, value: &str) -> Field {
+        self.metadata.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    pub fn is_nullable(&self) -> bool {
+        self.dtype.is_nullable()
+    }
+
+    //the dtype as in the schema grammar, e.g. decimal[18,4;null=NA]
+    pub fn descr(&self) -> String {
+        let args: Vec<String> = [self.args.clone(), self.options.to_args()]
+            .into_iter()
+            .filter(|x| !x.is_empty())
+            .collect();
+        if args.is_empty() {
+            self.dtype.type_name().to_string()
+        } else {
+            format!("{}[{}]", self.dtype.type_name(), args.join(";"))
+        }
+    }
+
+    pub fn from_column(col: &Column) -> Field {
+        Field {
+            name: col.name.clone(),
+            dtype: col.dtype(),
+            args: col.data.type_args(),
+            metadata: col.metadata.clone(),
+            options: col.options.clone(),
+        }
+    }
+
+    //empty column of this field
+    pub fn to_column(&self) -> Result<Column> {
+        let mut col = Column::new_with_args(self.name.clone(), self.dtype, &self.args)?;
+        col.metadata = self.metadata.clone();
+        col.options = self.options.clone();
+        Ok(col)
+    }
+}
+
+//Schema is the ordered fields of a data frame
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Schema {
+    pub fields: Vec<Field>,
+}
+
+impl Schema {
+    pub fn new(fields: Vec<Field>) -> Schema {
+        Schema { fields }
+    }
+
+    //schema of a string like "a:int,b:datetime[%Y-%m-%d]"
+    pub fn parse(schema: &str) -> Result<Schema> {
+        Schema::parse_with_width(schema, AliasWidth::default())
+    }
+
+    //as parse, but the int and double aliases resolve to 64-bit types when width is Wide
+    pub fn parse_with_width(schema: &str, width: AliasWidth) -> Result<Schema> {
+        let mut scanner = Scanner {
+            src: schema,
+            pos: 0,
+        };
+        let mut fields = Vec::new();
+        let mut errors: Vec<Box<dyn error::Error>> = Vec::new();
+
+        //one field per descriptor, a descriptor that fails is skipped up to the next separator
+        loop {
+            scanner.skip_blanks();
+            match scanner.peek() {
+                None => break,
+                Some('\n') => {
+                    scanner.bump();
+                    continue;
+                }
+                Some(',' | ';') => {
+                    errors.push(Box::new(
+                        scanner.error_at(scanner.pos, "empty column descriptor"),
+                    ));
+                    scanner.bump();
+                    continue;
+                }
+                _ => (),
+            }
+            match parse_field(&mut scanner, width) {
+                Ok(field) => fields.push(field),
+                Err(e) => {
+                    errors.push(Box::new(e));
+                    scanner.skip_descriptor();
+                }
+            }
+            scanner.bump();
+        }
+
+        if !errors.is_empty() {
+            Err(DFError {
+                error_msg: "failed to parse schema".to_string(),
+                sub_errors: errors,
+            })?;
+        }
+        Ok(Schema { fields })
+    }
+
+    pub fn field(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().find(|f| f.name.as_deref() == Some(name))
+    }
+
+    pub fn names(&self) -> Vec<Option<&str>> {
+        self.fields.iter().map(|f| f.name.as_deref()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    //empty columns of all fields
+    pub fn to_columns(&self) -> Result<Vec<Column>> {
+        self.fields.iter().map(Field::to_column).collect()
+    }
+
+    //schema of a json document like {"fields": [{"name": "a", "dtype": "int"}]}
+    pub fn from_json(doc: &str) -> Result<Schema> {
+        serde_json::from_str::<SchemaDoc>(doc)?.to_schema()
+    }
+
+    //schema of a toml document with a [[fields]] table per field
+    pub fn from_toml(doc: &str) -> Result<Schema> {
+        toml::from_str::<SchemaDoc>(doc)?.to_schema()
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&SchemaDoc::from_schema(self))?)
+    }
+
+    pub fn to_toml(&self) -> Result<String> {
+        Ok(toml::to_string(&SchemaDoc::from_schema(self))?)
+    }
+
+    //schema of a .json or .toml file, any other file holds a schema string
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Schema> {
+        let doc = fs::read_to_string(&path)?;
+        match schema_format(path.as_ref()) {
+            Some("json") => Schema::from_json(&doc),
+            Some("toml") => Schema::from_toml(&doc),
+            _ => Schema::parse(&doc),
+        }
+    }
+
+    //write the schema in the format of the file extension, as in from_file
+    pub fn write_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let doc = match schema_format(path.as_ref()) {
+            Some("json") => self.to_json()?,
+            Some("toml") => self.to_toml()?,
+            _ => self.to_string(),
+        };
+        fs::write(path, doc)?;
+        Ok(())
+    }
+}
+
+fn schema_format(path: &Path) -> Option<&str> {
+    path.extension().and_then(|x| x.to_str())
+}
+
+//the schema grammar, metadata is not part of it
+impl fmt::Display for Schema {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let descrs: Vec<String> = self
+            .fields
+            .iter()
+            .map(|x| match &x.name {
+                Some(name) => format!("{}:{}", quote_name(name), x.descr()),
+                None => x.descr(),
+            })
+            .collect();
+        write!(f, "{}", descrs.join(","))
+    }
+}
+
+//names with separators or quotes are quoted, quotes inside doubled
+fn quote_name(name: &str) -> String {
+    let needs_quotes =
+        name.is_empty() || name.trim() != name || name.contains(|c| ":,;\n#[]?\"".contains(c));
+    if needs_quotes {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    } else {
+        name.to_string()
+    }
+}
+
+//Scanner walks the schema string, pos is a byte offset
+struct Scanner<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.peek() == Some(c);
+        if found {
+            self.bump();
+        }
+        found
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let start = self.pos;
+        while self.peek().is_some_and(&f) {
+            self.bump();
+        }
+        &self.src[start..self.pos]
+    }
+
+    //spaces and comments from # to the end of the line
+    fn skip_blanks(&mut self) {
+        loop {
+            match self.peek() {
+                Some(' ' | '\t' | '\r') => {
+                    self.bump();
+                }
+                Some('#') => {
+                    self.take_while(|c| c != '\n');
+                }
+                _ => break,
+            }
+        }
+    }
+
+    //text inside balanced brackets, pos is at the '['
+    fn bracketed(&mut self) -> Option<&'a str> {
+        let start = self.pos + 1;
+        let mut depth = 0;
+        while let Some(c) = self.bump() {
+            match c {
+                '[' => depth += 1,
+                ']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(&self.src[start..self.pos - 1]);
+                    }
+                }
+                _ => (),
+            }
+        }
+        None
+    }
+
+    //a name in double quotes, "" stands for one quote
+    fn quoted_name(&mut self) -> std::result::Result<String, SchemaError> {
+        let open = self.pos;
+        self.bump();
+        let mut out = String::new();
+        loop {
+            match self.bump() {
+                None => return Err(self.error_at(open, "quote is not closed")),
+                Some('"') if self.eat('"') => out.push('"'),
+                Some('"') => return Ok(out),
+                Some(c) => out.push(c),
+            }
+        }
+    }
+
+    //up to the next separator outside brackets and quotes
+    fn skip_descriptor(&mut self) {
+        let mut depth: usize = 0;
+        let mut quoted = false;
+        while let Some(c) = self.peek() {
+            match c {
+                '"' => quoted = !quoted,
+                '[' if !quoted => depth += 1,
+                ']' if !quoted => depth = depth.saturating_sub(1),
+                ',' | ';' | '\n' if !quoted && depth == 0 => return,
+                _ => (),
+            }
+            self.bump();
+        }
+    }
+
+    fn error_at(&self, pos: usize, error_msg: &str) -> SchemaError {
+        SchemaError::new(self.src, pos, error_msg)
+    }
+}
+
+//message of a column error without the ColError wrapping, for a SchemaError
+fn describe(e: Box<dyn error::Error>) -> String {
+    match e.downcast_ref::<ColError>() {
+        Some(ColError {
+            errorcode: ColErrorcode::ParseDataType,
+            error_msg,
+        }) => format!("do not recognize \"{}\" as datatype", error_msg),
+        Some(col_error) => col_error.error_msg.clone(),
+        None => e.to_string(),
+    }
+}
+
+//one descriptor, [name:]type[?][[args;null=..;default=..]][?]
+fn parse_field(sc: &mut Scanner, width: AliasWidth) -> std::result::Result<Field, SchemaError> {
+    let name = if sc.peek() == Some('"') {
+        let name = sc.quoted_name()?;
+        sc.skip_blanks();
+        if !sc.eat(':') {
+            return Err(sc.error_at(sc.pos, "expected ':' after the quoted name"));
+        }
+        Some(name)
+    } else {
+        //a bare word is the name if a : follows, else it is the type
+        let word_start = sc.pos;
+        let word = sc.take_while(|c| !":,;\n#[]?\"".contains(c)).trim();
+        if sc.eat(':') {
+            if word.is_empty() {
+                return Err(sc.error_at(word_start, "empty column name"));
+            }
+            Some(word.to_string())
+        } else {
+            sc.pos = word_start;
+            None
+        }
+    };
+
+    sc.skip_blanks();
+    let type_start = sc.pos;
+    let type_name = sc.take_while(|c| c.is_alphanumeric() || c == '_');
+    if type_name.is_empty() {
+        return Err(match sc.peek() {
+            Some(c) if !",;\n".contains(c) => {
+                sc.error_at(sc.pos, &format!("expected a dtype, got '{}'", c))
+            }
+            _ => sc.error_at(sc.pos, "expected a dtype"),
+        });
+    }
+    let mut nullable = sc.eat('?');
+    let mut args = "";
+    let mut args_start = sc.pos;
+    if sc.peek() == Some('[') {
+        let open = sc.pos;
+        args_start = open + 1;
+        args = sc
+            .bracketed()
+            .ok_or_else(|| sc.error_at(open, "'[' is not closed"))?;
+    }
+    nullable = nullable || sc.eat('?');
+    sc.skip_blanks();
+    match sc.peek() {
+        None | Some(',' | ';' | '\n') => (),
+        Some(':') => {
+            return Err(sc.error_at(
+                sc.pos,
+                "unexpected ':' after the dtype, quote names that contain ':'",
+            ))
+        }
+        Some(c) => return Err(sc.error_at(sc.pos, &format!("unexpected '{}' after the dtype", c))),
+    }
+
+    //null tokens and defaults are options of the column, the other arguments are the dtype's
+    let mut options = CellOptions::default();
+    let mut default_pos = args_start;
+    let mut type_args = Vec::new();
+    for item in split_outside_brackets(args, &[';']) {
+        let item_pos = args_start + (item.as_ptr() as usize - args.as_ptr() as usize);
+        if let Some(tokens) = item.trim().strip_prefix("null=") {
+            options.null_tokens = tokens.split('|').map(|t| t.trim().to_string()).collect();
+        } else if let Some(default) = item.trim().strip_prefix("default=") {
+            options.default = Some(default.trim().to_string());
+            default_pos = item_pos;
+        } else if !item.trim().is_empty() {
+            type_args.push(item);
+        }
+    }
+
+    let mut descr = String::from(type_name);
+    if nullable {
+        descr.push('?');
+    }
+    if !type_args.is_empty() {
+        descr = format!("{}[{}]", descr, type_args.join(";"));
+    }
+    let mut col = Column::from_descr(name, &descr, width)
+        .map_err(|e| sc.error_at(type_start, &describe(e)))?;
+    check_default(&col, &options)
+        .map_err(|e| sc.error_at(default_pos, &format!("bad default, {}", describe(e))))?;
+    col.options = options;
+    Ok(Field::from_column(&col))
+}
+
+//the default has to parse in the column
+fn check_default(col: &Column, options: &CellOptions) -> Result<()> {
+    match &options.default {
+        Some(default) => col.data.boxed_clone().push_from_str(default),
+        None => Ok(()),
+    }
+}
+
+//FieldDoc is a field as written in json or toml schema documents, dtype is in the schema
+//grammar and nullability is a flag, e.g. {name = "ts", dtype = "datetime[%Y]", nullable = true}
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct FieldDoc {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    dtype: String,
+    #[serde(default)]
+    nullable: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    null: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    metadata: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct SchemaDoc {
+    fields: Vec<FieldDoc>,
+}
+
+impl FieldDoc {
+    fn from_field(field: &Field) -> FieldDoc {
+        let type_name = field.dtype.non_nullable().type_name();
+        FieldDoc {
+            name: field.name.clone(),
+            dtype: if field.args.is_empty() {
+                type_name.to_string()
+            } else {
+                format!("{}[{}]", type_name, field.args)
+            },
+            nullable: field.is_nullable(),
+            null: field.options.null_tokens.clone(),
+            default: field.options.default.clone(),
+            metadata: field.metadata.clone(),
+        }
+    }
+
+    fn to_field(&self) -> Result<Field> {
+        let descr = if self.nullable {
+            format!("{}?", self.dtype.trim())
+        } else {
+            self.dtype.clone()
+        };
+        let mut col = Column::from_descr(self.name.clone(), &descr, AliasWidth::default())?;
+        col.options = CellOptions {
+            null_tokens: self.null.clone(),
+            default: self.default.clone(),
+        };
+        check_default(&col, &col.options)?;
+        col.metadata = self.metadata.clone();
+        Ok(Field::from_column(&col))
+    }
+}
+
+impl SchemaDoc {
+    fn from_schema(schema: &Schema) -> SchemaDoc {
+        SchemaDoc {
+            fields: schema.fields.iter().map(FieldDoc::from_field).collect(),
+        }
+    }
+
+    //all fields that fail are reported
+    fn to_schema(&self) -> Result<Schema> {
+        let mut fields = Vec::new();
+        let mut errors: Vec<Box<dyn error::Error>> = Vec::new();
+        for (i, field_doc) in self.fields.iter().enumerate() {
+            match field_doc.to_field() {
+                Ok(field) => fields.push(field),
+                Err(e) => errors.push(Box::new(ColError {
+                    errorcode: ColErrorcode::SchemaSyntax,
+                    error_msg: format!(
+                        "field No. {} \"{}\": {}",
+                        i,
+                        field_doc.name.as_deref().unwrap_or("None"),
+                        describe(e)
+                    ),
+                })),
+            }
+        }
+        if !errors.is_empty() {
+            Err(DFError {
+                error_msg: "failed to parse schema".to_string(),
+                sub_errors: errors,
+            })?;
+        }
+        Ok(Schema { fields })
+    }
+}
+
+//ToSchema lets the readers take a schema string as well as a Schema
+pub trait ToSchema {
+    fn to_schema(&self) -> Result<Schema>;
+}
+
+impl ToSchema for str {
+    fn to_schema(&self) -> Result<Schema> {
+        Schema::parse(self)
+    }
+}
+
+impl ToSchema for String {
+    fn to_schema(&self) -> Result<Schema> {
+        Schema::parse(self)
+    }
+}
+
+impl ToSchema for Schema {
+    fn to_schema(&self) -> Result<Schema> {
+        Ok(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataframe::column::{TimeUnit, Value};
+    use crate::dataframe::{csv_read_str_iter, DataFrame};
+
+    #[test]
+    fn schema_round_trip() {
+        let myschema = "id:int,ts:datetimeNullable[%Y-%m-%d %H:%M;unit=ms],amount:decimal[18,4],\
+                        v:list[intNullable],payload:binary[base64],p:struct[x:f64,y:stringNullable]";
+        let schema = Schema::parse(myschema).unwrap();
+        assert_eq!(schema.to_string(), myschema);
+        assert_eq!(Schema::parse(&schema.to_string()).unwrap(), schema);
+
+        let ts = schema.field("ts").unwrap();
+        assert_eq!(ts.dtype, Dtype::ColDatetimeNullable(TimeUnit::Milliseconds));
+        assert!(ts.is_nullable());
+        assert!(!schema.field("id").unwrap().is_nullable());
+
+        let df = DataFrame::new(&schema).unwrap();
+        assert_eq!(df.schema(), schema);
+        assert_eq!(DataFrame::new(myschema).unwrap().schema(), schema);
+
+        //defaults are left out
+        let schema = Schema::parse("a:datetime[unit=us],b:binary[hex],c:decimal").unwrap();
+        assert_eq!(schema.to_string(), "a:datetime,b:binary,c:decimal");
+    }
+
+    #[test]
+    fn schema_grammar() {
+        let myschema = "# readings, one column per line
+            \"station:id\": u32      # quoted names may contain separators
+            someOtherName: int?
+            ts: datetime[%d.%m.%Y %H:%M]?;
+            temp: double[null=NA|-;default=0]
+            \"say \"\"hi\"\"\": string,";
+        let schema = Schema::parse(myschema).unwrap();
+        assert_eq!(
+            schema.names(),
+            vec![
+                Some("station:id"),
+                Some("someOtherName"),
+                Some("ts"),
+                Some("temp"),
+                Some("say \"hi\"")
+            ]
+        );
+        assert_eq!(schema.fields[1].dtype, Dtype::ColIntNullable);
+        assert_eq!(
+            schema.fields[2].dtype,
+            Dtype::ColDatetimeNullable(TimeUnit::Microseconds)
+        );
+        assert_eq!(
+            schema.to_string(),
+            "\"station:id\":u32,someOtherName:intNullable,ts:datetimeNullable[%d.%m.%Y %H:%M],\
+             temp:double[null=NA|-;default=0],\"say \"\"hi\"\"\":string"
+        );
+        assert_eq!(Schema::parse(&schema.to_string()).unwrap(), schema);
+
+        //null tokens and defaults apply to the cells
+        let df = csv_read_str_iter("1,,,NA,x\n2,3,,-1.5,y", &schema).unwrap();
+        assert_eq!(df.column("temp").unwrap().as_f32().unwrap(), &[0.0, -1.5]);
+        assert_eq!(
+            df.column("someOtherName").unwrap().data.to_string(),
+            "NA, 3, "
+        );
+
+        assert!(Schema::parse("a:int[default=x]").is_err());
+        assert!(Schema::parse("a:int,\n\nb:int,").is_ok());
+    }
+
+    #[test]
+    fn schema_nullable_suffix() {
+        let types = [
+            "int",
+            "double",
+            "string",
+            "stringPool",
+            "bool",
+            "i64",
+            "u32",
+            "u64",
+            "f64",
+            "date",
+            "datetime",
+            "duration",
+            "categorical",
+            "decimal[6,2]",
+            "list[int]",
+            "struct[x:int]",
+            "binary",
+        ];
+        for t in types.iter() {
+            let schema = Schema::parse(&format!("a:{}?", t)).unwrap();
+            let dtype = schema.fields[0].dtype;
+            assert!(dtype.is_nullable(), "{} is nullable", t);
+            assert_eq!(
+                dtype,
+                Schema::parse(&format!("a:{}", t)).unwrap().fields[0]
+                    .dtype
+                    .nullable()
+            );
+            assert_eq!(Schema::parse(&schema.to_string()).unwrap(), schema);
+
+            //an empty cell is a null of every type
+            let df = csv_read_str_iter("\n", &schema).unwrap();
+            assert_eq!(df.data[0].data.get_value(0), Value::Null, "{}", t);
+        }
+
+        let schema = Schema::parse("l:list[int]?,s:struct[x:int,tags:list[string]]?").unwrap();
+        assert_eq!(
+            schema.to_string(),
+            "l:listNullable[int],s:structNullable[x:intNullable,tags:listNullable[string]]"
+        );
+        let df = csv_read_str_iter("\"[1,2]\",\n[],null\nnull,\"{\"\"x\"\":3}\"", &schema).unwrap();
+        assert_eq!(df.data[0].data.to_string(), "[1, 2], [], NA, ");
+        assert_eq!(df.data[1].data.to_string(), "NA, NA, {x: 3, tags: NA}, ");
+    }
+
+    #[test]
+    fn schema_error_spans() {
+        let err = Schema::parse("a:int,\n  \"b:int\nc:int")
+            .unwrap_err()
+            .to_string();
+        assert!(err.ends_with("quote is not closed at line 2, column 3\n    \"b:int\n    ^"));
+
+        let err = Schema::parse("a:int,b:list[int").unwrap_err().to_string();
+        assert!(err.contains("'[' is not closed at line 1, column 13"));
+
+        let err = Schema::parse("a:int\nb:int x").unwrap_err().to_string();
+        assert!(err.contains("unexpected 'x' after the dtype at line 2, column 7"));
+
+        let err = Schema::parse("a:decimal[4,18]").unwrap_err().to_string();
+        assert!(err.contains("at line 1, column 3"));
+    }
+
+    #[test]
+    fn schema_documents() {
+        let doc = r#"
+            [[fields]]
+            name = "id"
+            dtype = "u32"
+
+            [[fields]]
+            name = "ts"
+            dtype = "datetime[%Y-%m-%d %H:%M;unit=ms]"
+            nullable = true
+            null = ["NA", "-"]
+
+            [[fields]]
+            name = "temp"
+            dtype = "decimal[6,2]"
+            default = "0"
+            metadata = { unit = "celsius" }
+        "#;
+        let schema = Schema::from_toml(doc).unwrap();
+        assert_eq!(
+            schema.to_string(),
+            "id:u32,ts:datetimeNullable[%Y-%m-%d %H:%M;unit=ms;null=NA|-],temp:decimal[6,2;default=0]"
+        );
+        assert_eq!(schema.fields[2].metadata["unit"], "celsius");
+
+        //export and read back in both formats
+        assert_eq!(
+            Schema::from_toml(&schema.to_toml().unwrap()).unwrap(),
+            schema
+        );
+        let json = schema.to_json().unwrap();
+        assert!(json.contains("\"nullable\": true"));
+        assert_eq!(Schema::from_json(&json).unwrap(), schema);
+
+        let df = csv_read_str_iter("1,NA,\n2,2021-03-04 05:06,21.5", &schema).unwrap();
+        assert_eq!(df.column("temp").unwrap().data.to_string(), "0.00, 21.50, ");
+        let path = std::env::temp_dir().join("teddies_schema_documents.json");
+        df.write_schema_file(&path).unwrap();
+        let df2 = DataFrame::from_schema_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(df2.schema(), schema);
+
+        //every field that does not parse is reported
+        let doc = r#"{"fields": [{"name": "a", "dtype": "int"}, {"name": "b", "dtype": "nope"},
+            {"name": "c", "dtype": "int[x]", "nullable": true}]}"#;
+        let err = Schema::from_json(doc).unwrap_err().to_string();
+        assert!(err.contains("field No. 1 \"b\": do not recognize \"nope\" as datatype"));
+        assert!(err.contains("field No. 2 \"c\""));
+    }
+
+    #[test]
+    fn schema_metadata() {
+        let schema = Schema::new(vec![
+            Field::new(Some(String::from("t")), Dtype::ColDouble)
+                .with_metadata("unit", "celsius")
+                .with_metadata("description", "air temperature"),
+            Field::new(Some(String::from("d")), Dtype::ColDecimalNullable(6, 2)),
+        ]);
+        assert_eq!(schema.to_string(), "t:double,d:decimalNullable[6,2]");
+
+        //readers take the Schema and the columns keep the metadata
+        let df = csv_read_str_iter("21.5,1.25\n19,", &schema).unwrap();
+        assert_eq!(df.data[0].metadata["unit"], "celsius");
+        assert_eq!(df.schema(), schema);
+
+        let serialized = serde_json::to_string(&schema).unwrap();
+        let schema2: Schema = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(schema2, schema);
+        let serialized = serde_json::to_string(&df).unwrap();
+        let df2: DataFrame = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(df2.schema(), schema);
+    }
+}
@@ -0,0 +1,165 @@
+use super::column::Value;
+use super::{DataFrame, Result};
+use std::cmp::Ordering;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SortOrder {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+//where nulls go, independent of the sort order
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum NullPlacement {
+    First,
+    #[default]
+    Last,
+}
+
+//order of two non-null values of one column. NaN is greater than all numbers, lists
+//compare element by element and structs field by field, nulls inside them go first
+pub fn total_cmp(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Double(x), Value::Double(y)) => match (x.is_nan(), y.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            _ => x.partial_cmp(y).unwrap_or(Ordering::Equal),
+        },
+        (Value::List(ca, sa, ea), Value::List(cb, sb, eb)) => (*sa..*ea)
+            .zip(*sb..*eb)
+            .map(|(i, j)| {
+                compare(
+                    &ca.get_value(i),
+                    &cb.get_value(j),
+                    SortOrder::Ascending,
+                    NullPlacement::First,
+                )
+            })
+            .find(|o| o.is_ne())
+            .unwrap_or_else(|| (ea - sa).cmp(&(eb - sb))),
+        (Value::Struct(fa, ra), Value::Struct(fb, rb)) => fa
+            .iter()
+            .zip(fb.iter())
+            .map(|(x, y)| {
+                let (x, y) = (x.data.get_value(*ra), y.data.get_value(*rb));
+                compare(&x, &y, SortOrder::Ascending, NullPlacement::First)
+            })
+            .find(|o| o.is_ne())
+            .unwrap_or(Ordering::Equal),
+        _ => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+    }
+}
+
+//order of two cells, nulls are placed by nulls whatever the order
+pub fn compare(a: &Value, b: &Value, order: SortOrder, nulls: NullPlacement) -> Ordering {
+    let nulls_first = nulls == NullPlacement::First;
+    match (a, b) {
+        (Value::Null, Value::Null) => Ordering::Equal,
+        (Value::Null, _) if nulls_first => Ordering::Less,
+        (Value::Null, _) => Ordering::Greater,
+        (_, Value::Null) if nulls_first => Ordering::Greater,
+        (_, Value::Null) => Ordering::Less,
+        _ if order == SortOrder::Descending => total_cmp(a, b).reverse(),
+        _ => total_cmp(a, b),
+    }
+}
+
+impl DataFrame {
+    //row order sorting by the keys, later keys break ties of earlier ones, equal rows keep
+    //their order
+    pub fn argsort_by(
+        &self,
+        keys: &[(&str, SortOrder)],
+        nulls: NullPlacement,
+    ) -> Result<Vec<usize>> {
+        let cols = keys
+            .iter()
+            .map(|(name, order)| Ok((self.column(name)?.data.as_ref(), *order)))
+            .collect::<Result<Vec<_>>>()?;
+        let mut indices: Vec<usize> = (0..self.shape().0).collect();
+        indices.sort_by(|&i, &j| {
+            cols.iter()
+                .map(|(data, order)| compare(&data.get_value(i), &data.get_value(j), *order, nulls))
+                .find(|o| o.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+        Ok(indices)
+    }
+
+    //rows sorted by the keys with nulls last, e.g.
+    //df.sort_by(&[("a", SortOrder::Ascending), ("b", SortOrder::Descending)])
+    pub fn sort_by(&self, keys: &[(&str, SortOrder)]) -> Result<DataFrame> {
+        self.sort_by_with_nulls(keys, NullPlacement::Last)
+    }
+
+    pub fn sort_by_with_nulls(
+        &self,
+        keys: &[(&str, SortOrder)],
+        nulls: NullPlacement,
+    ) -> Result<DataFrame> {
+        self.take(&self.argsort_by(keys, nulls)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataframe::csv_read_str_iter;
+
+    #[test]
+    fn sort_by_keys() {
+        let mycsvstr = "b,2,1.5\na,,0.5\nb,1,NaN\na,2,\nb,2,-1";
+        let df =
+            csv_read_str_iter(mycsvstr, "k:stringPool,n:intNullable,x:doubleNullable").unwrap();
+
+        let sorted = df
+            .sort_by(&[("k", SortOrder::Ascending), ("n", SortOrder::Descending)])
+            .unwrap();
+        assert_eq!(
+            sorted.column("k").unwrap().data.to_string(),
+            "[ \"a\", \"a\", \"b\", \"b\", \"b\",]"
+        );
+        assert_eq!(
+            sorted.column("n").unwrap().data.to_string(),
+            "2, NA, 2, 2, 1, "
+        );
+        //ties keep the input order
+        assert_eq!(
+            sorted.column("x").unwrap().data.to_string(),
+            "NA, 0.5, 1.5, -1, NaN, "
+        );
+
+        let sorted = df
+            .sort_by_with_nulls(&[("n", SortOrder::Ascending)], NullPlacement::First)
+            .unwrap();
+        assert_eq!(
+            sorted.column("n").unwrap().data.to_string(),
+            "NA, 1, 2, 2, 2, "
+        );
+        assert!(df.sort_by(&[("nope", SortOrder::Ascending)]).is_err());
+
+        //NaN is the largest number, nulls stay last in both orders
+        let x = df.column("x").unwrap().data.as_ref();
+        assert_eq!(
+            x.argsort(SortOrder::Ascending, NullPlacement::Last),
+            vec![4, 1, 0, 2, 3]
+        );
+        assert_eq!(
+            x.argsort(SortOrder::Descending, NullPlacement::Last),
+            vec![2, 0, 1, 4, 3]
+        );
+    }
+
+    #[test]
+    fn sort_nested() {
+        let df =
+            csv_read_str_iter("\"[2,1]\"\n\"[1,2,3]\"\n\"[1,2]\"\n\"[]\"", "l:list[int]").unwrap();
+        let l = df.column("l").unwrap().data.as_ref();
+        assert_eq!(
+            l.argsort(SortOrder::Ascending, NullPlacement::Last),
+            vec![3, 2, 1, 0]
+        );
+    }
+}
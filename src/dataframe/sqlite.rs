@@ -0,0 +1,607 @@
+use super::cast::{self, Number};
+use super::column::{
+    ColBinary, ColBinaryNullable, ColBool, ColBoolNullable, ColCategorical, ColDouble,
+    ColDoubleNullable, ColF64, ColF64Nullable, ColI64, ColI64Nullable, ColInt, ColIntNullable,
+    ColString, ColStringNullable, ColStringPool, ColStringPoolNullable, ColU32, ColU32Nullable,
+    ColU64, ColU64Nullable, Column, Dtype, TimeUnit, Value, VectorData,
+};
+use super::{decimal, nested, temporal};
+use super::{DFError, DataFrame, Result, ToSchema};
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{params_from_iter, Connection};
+use std::borrow::Cow;
+
+//quote an identifier for sql, inner double quotes are doubled
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn sql_type(dtype: Dtype) -> String {
+    let sql = match dtype {
+        Dtype::ColInt => "INTEGER NOT NULL",
+        Dtype::ColIntNullable => "INTEGER",
+        Dtype::ColDouble => "REAL NOT NULL",
+        Dtype::ColDoubleNullable => "REAL",
+        Dtype::ColString | Dtype::ColStringPool | Dtype::ColCategorical => "TEXT NOT NULL",
+        Dtype::ColStringNullable | Dtype::ColStringPoolNullable | Dtype::ColCategoricalNullable => {
+            "TEXT"
+        }
+        //lists and structs are stored as json text
+        Dtype::ColList | Dtype::ColStruct => "TEXT NOT NULL",
+        Dtype::ColListNullable | Dtype::ColStructNullable => "TEXT",
+        Dtype::ColBinary => "BLOB NOT NULL",
+        Dtype::ColBinaryNullable => "BLOB",
+        Dtype::ColBool => "BOOLEAN NOT NULL",
+        Dtype::ColBoolNullable => "BOOLEAN",
+        Dtype::ColI64 | Dtype::ColU32 | Dtype::ColU64 => "INTEGER NOT NULL",
+        Dtype::ColI64Nullable | Dtype::ColU32Nullable | Dtype::ColU64Nullable => "INTEGER",
+        Dtype::ColF64 => "REAL NOT NULL",
+        Dtype::ColF64Nullable => "REAL",
+        Dtype::ColDate => "DATE NOT NULL",
+        Dtype::ColDateNullable => "DATE",
+        Dtype::ColDatetime(_) => "DATETIME NOT NULL",
+        Dtype::ColDatetimeNullable(_) => "DATETIME",
+        Dtype::ColDuration(_) => "INTEGER NOT NULL",
+        Dtype::ColDurationNullable(_) => "INTEGER",
+        //TEXT in the type name gives text affinity, so sqlite keeps decimals exact
+        Dtype::ColDecimal(p, s) => return format!("DECIMAL_TEXT({},{}) NOT NULL", p, s),
+        Dtype::ColDecimalNullable(p, s) => return format!("DECIMAL_TEXT({},{})", p, s),
+    };
+    sql.to_string()
+}
+
+//precision and scale of a declared type like DECIMAL(18,4)
+fn decimal_args_from_decl(decl: &str) -> Option<(u8, u8)> {
+    let (_, args) = decl.split_once('(')?;
+    decimal::parse_decimal_args(args.strip_suffix(')')?).ok()
+}
+
+//map a declared sql column type to a dtype by sqlite's type affinity rules
+fn dtype_from_decl(decl: &str) -> Dtype {
+    let decl = decl.to_uppercase();
+    if decl.contains("BLOB") {
+        Dtype::ColBinaryNullable
+    } else if decl.contains("BOOL") {
+        Dtype::ColBoolNullable
+    } else if decl.contains("DATETIME") || decl.contains("TIMESTAMP") {
+        Dtype::ColDatetimeNullable(TimeUnit::Microseconds)
+    } else if decl.contains("DATE") {
+        Dtype::ColDateNullable
+    } else if decl.contains("DEC") || decl.contains("NUMERIC") {
+        match decimal_args_from_decl(&decl) {
+            Some((p, s)) => Dtype::ColDecimalNullable(p, s),
+            None => Dtype::ColF64Nullable,
+        }
+    } else if decl.contains("INT") {
+        Dtype::ColI64Nullable
+    } else if decl.contains("REAL") || decl.contains("FLOA") || decl.contains("DOUB") {
+        Dtype::ColF64Nullable
+    } else {
+        Dtype::ColStringNullable
+    }
+}
+
+//expressions like count(*) have no declared type, then the first value decides
+fn dtype_from_value(value: &SqlValue) -> Dtype {
+    match value {
+        SqlValue::Integer(_) => Dtype::ColI64Nullable,
+        SqlValue::Real(_) => Dtype::ColF64Nullable,
+        SqlValue::Blob(_) => Dtype::ColBinaryNullable,
+        _ => Dtype::ColStringNullable,
+    }
+}
+
+fn to_sql_value(value: Value) -> Result<SqlValue> {
+    let sql = match value {
+        Value::Null => SqlValue::Null,
+        Value::Int(x) => SqlValue::Integer(x),
+        //sqlite integers are signed 64 bit, larger u64 cannot be stored as numbers
+        Value::UInt(x) => SqlValue::Integer(i64::try_from(x).map_err(|_| DFError {
+            error_msg: format!("{} does not fit a sqlite integer", x),
+            sub_errors: Vec::new(),
+        })?),
+        Value::Double(x) => SqlValue::Real(x),
+        Value::Str(x) => SqlValue::Text(x.to_string()),
+        Value::Bool(x) => SqlValue::Integer(x as i64),
+        //dates and datetimes are stored as iso text in UTC, durations as ticks
+        Value::Date(x) => SqlValue::Text(temporal::format_date(x, None)?),
+        Value::Datetime(x, unit) => SqlValue::Text(temporal::format_datetime(x, None, unit, None)?),
+        Value::Duration(x, _) => SqlValue::Integer(x),
+        //text keeps decimals exact
+        Value::Decimal(x) => SqlValue::Text(x.to_string()),
+        Value::Binary(x) => SqlValue::Blob(x.to_vec()),
+        Value::List(..) | Value::Struct(..) => {
+            SqlValue::Text(nested::value_to_json(value).to_string())
+        }
+    };
+    Ok(sql)
+}
+
+//the text of a cell, None for NULL. blobs are text only if they are utf-8
+fn sql_text(value: &SqlValue) -> Result<Option<Cow<'_, str>>> {
+    let text = match value {
+        SqlValue::Null => return Ok(None),
+        SqlValue::Integer(x) => Cow::from(x.to_string()),
+        SqlValue::Real(x) => Cow::from(x.to_string()),
+        SqlValue::Text(x) => Cow::from(x),
+        SqlValue::Blob(x) => Cow::from(std::str::from_utf8(x).map_err(|_| DFError {
+            error_msg: format!("BLOB of {} bytes is not utf-8 text", x.len()),
+            sub_errors: Vec::new(),
+        })?),
+    };
+    Ok(Some(text))
+}
+
+fn sql_number<T: Number>(value: &SqlValue, dtype: Dtype) -> Result<Option<T>> {
+    match value {
+        SqlValue::Integer(x) => cast::to_number(Value::Int(*x), dtype),
+        SqlValue::Real(x) => cast::to_number(Value::Double(*x), dtype),
+        _ => Ok(None),
+    }
+}
+
+//a cell parsed by push_from_str of the chunk. NULL is the null of nullable columns and the
+//default of others, without a default it is an error
+fn push_sql_text<T: VectorData + ?Sized>(
+    column: &Column,
+    chunk: &mut T,
+    value: &SqlValue,
+) -> Result<()> {
+    match sql_text(value)? {
+        Some(x) => chunk.push_from_str(column.options.apply(&x)),
+        None if column.dtype().is_nullable() || column.options.default.is_some() => {
+            chunk.push_from_str(column.options.apply(""))
+        }
+        None => Err(Box::new(DFError {
+            error_msg: format!(
+                "NULL in column {} of non-nullable dtype {}",
+                column.name.as_deref().unwrap_or("None"),
+                column.dtype()
+            ),
+            sub_errors: Vec::new(),
+        })),
+    }
+}
+
+//the cells of one result column go to a chunk of the column's type, which then extends it.
+//typed pushes the cells that map onto its values and returns false for the others, which are
+//parsed as text
+fn push_sql_cells<T: VectorData>(
+    column: &mut Column,
+    mut chunk: T,
+    cells: &[&SqlValue],
+    typed: impl Fn(&mut T, &SqlValue) -> Result<bool>,
+) -> Result<()> {
+    chunk.reserve(cells.len());
+    for cell in cells.iter() {
+        if !typed(&mut chunk, cell)? {
+            push_sql_text(column, &mut chunk, cell)?;
+        }
+    }
+    column.data.extend(&chunk)
+}
+
+//integers and reals into numeric columns, in the range of the column type
+macro_rules! push_sql_numbers {
+    ($column:expr, $cells:expr, $col:ident, $col_nullable:ident) => {{
+        let dtype = $column.dtype();
+        if dtype.is_nullable() {
+            push_sql_cells($column, $col_nullable::default(), $cells, |c, v| {
+                let Some(x) = sql_number(v, dtype)? else {
+                    return Ok(false);
+                };
+                c.push(Some(x));
+                Ok(true)
+            })
+        } else {
+            push_sql_cells($column, $col::default(), $cells, |c, v| {
+                let Some(x) = sql_number(v, dtype)? else {
+                    return Ok(false);
+                };
+                c.data.push(x);
+                Ok(true)
+            })
+        }
+    }};
+}
+
+//strings take any cell as text, so an empty TEXT stays an empty string
+macro_rules! push_sql_strings {
+    ($column:expr, $cells:expr, $chunk:expr, $push:expr) => {{
+        push_sql_cells($column, $chunk, $cells, |c, v| {
+            let Some(x) = sql_text(v)? else {
+                return Ok(false);
+            };
+            $push(c, &x);
+            Ok(true)
+        })
+    }};
+}
+
+//integers are bools where 0 and 1, as to_sqlite writes them, blobs are binary values. other
+//cells and all cells of dtypes without such a mapping, e.g. dates, are parsed as text
+fn push_sql_column(column: &mut Column, cells: &[&SqlValue]) -> Result<()> {
+    match column.dtype() {
+        Dtype::ColInt | Dtype::ColIntNullable => {
+            push_sql_numbers!(column, cells, ColInt, ColIntNullable)
+        }
+        Dtype::ColI64 | Dtype::ColI64Nullable => {
+            push_sql_numbers!(column, cells, ColI64, ColI64Nullable)
+        }
+        Dtype::ColU32 | Dtype::ColU32Nullable => {
+            push_sql_numbers!(column, cells, ColU32, ColU32Nullable)
+        }
+        Dtype::ColU64 | Dtype::ColU64Nullable => {
+            push_sql_numbers!(column, cells, ColU64, ColU64Nullable)
+        }
+        Dtype::ColDouble | Dtype::ColDoubleNullable => {
+            push_sql_numbers!(column, cells, ColDouble, ColDoubleNullable)
+        }
+        Dtype::ColF64 | Dtype::ColF64Nullable => {
+            push_sql_numbers!(column, cells, ColF64, ColF64Nullable)
+        }
+        Dtype::ColString => {
+            let chunk = ColString::default();
+            push_sql_strings!(column, cells, chunk, |c: &mut ColString, x: &str| c
+                .data
+                .push(x.to_string()))
+        }
+        Dtype::ColStringNullable => {
+            let chunk = ColStringNullable::default();
+            push_sql_strings!(column, cells, chunk, |c: &mut ColStringNullable, x| c
+                .push(Some(x)))
+        }
+        Dtype::ColStringPool => {
+            let chunk = ColStringPool::default();
+            push_sql_strings!(column, cells, chunk, |c: &mut ColStringPool, x| c
+                .data
+                .add_str(x))
+        }
+        Dtype::ColStringPoolNullable => {
+            let chunk = ColStringPoolNullable::default();
+            push_sql_strings!(column, cells, chunk, |c: &mut ColStringPoolNullable, x| c
+                .push(Some(x)))
+        }
+        Dtype::ColCategorical => {
+            let chunk = ColCategorical::new();
+            push_sql_strings!(column, cells, chunk, |c: &mut ColCategorical, x| c
+                .push_str(x))
+        }
+        Dtype::ColCategoricalNullable => {
+            let chunk = ColCategorical::new().with_nulls();
+            push_sql_strings!(column, cells, chunk, |c: &mut ColCategorical, x| c
+                .push_str(x))
+        }
+        Dtype::ColBool => {
+            let chunk = ColBool::with_tokens(column.get::<ColBool>()?.tokens.clone());
+            push_sql_cells(column, chunk, cells, |c, v| match v {
+                SqlValue::Integer(x @ (0 | 1)) => {
+                    c.data.push(*x == 1);
+                    Ok(true)
+                }
+                _ => Ok(false),
+            })
+        }
+        Dtype::ColBoolNullable => {
+            let tokens = column.get::<ColBoolNullable>()?.tokens.clone();
+            let chunk = ColBoolNullable::with_tokens(tokens);
+            push_sql_cells(column, chunk, cells, |c, v| match v {
+                SqlValue::Integer(x @ (0 | 1)) => {
+                    c.data.push(*x == 1);
+                    c.validity.push(true);
+                    Ok(true)
+                }
+                _ => Ok(false),
+            })
+        }
+        //text in binary columns is in their own encoding
+        Dtype::ColBinary => {
+            let chunk = ColBinary::new(column.get::<ColBinary>()?.encoding);
+            push_sql_cells(column, chunk, cells, |c, v| match v {
+                SqlValue::Blob(x) => {
+                    c.push(x);
+                    Ok(true)
+                }
+                _ => Ok(false),
+            })
+        }
+        Dtype::ColBinaryNullable => {
+            let chunk = ColBinaryNullable::new(column.get::<ColBinaryNullable>()?.encoding);
+            push_sql_cells(column, chunk, cells, |c, v| match v {
+                SqlValue::Blob(x) => {
+                    c.push(Some(x));
+                    Ok(true)
+                }
+                _ => Ok(false),
+            })
+        }
+        dtype => {
+            let mut chunk = Column::new_with_args(None, dtype, &column.data.type_args())?.data;
+            chunk.reserve(cells.len());
+            for cell in cells.iter() {
+                push_sql_text(column, chunk.as_mut(), cell)?;
+            }
+            column.data.extend(chunk.as_ref())
+        }
+    }
+}
+
+impl DataFrame {
+    //create table from the column dtypes and insert all rows in one transaction
+    pub fn to_sqlite(&self, conn: &mut Connection, table: &str) -> Result<()> {
+        let names: Vec<String> = self
+            .data
+            .iter()
+            .enumerate()
+            .map(|(i, col)| quote_ident(&col.name.clone().unwrap_or_else(|| format!("col_{}", i))))
+            .collect();
+        let col_defs: Vec<String> = names
+            .iter()
+            .zip(self.data.iter())
+            .map(|(name, col)| format!("{} {}", name, sql_type(col.dtype())))
+            .collect();
+        let placeholders = vec!["?"; names.len()].join(", ");
+
+        let tx = conn.transaction()?;
+        tx.execute(
+            &format!(
+                "CREATE TABLE {} ({})",
+                quote_ident(table),
+                col_defs.join(", ")
+            ),
+            [],
+        )?;
+        {
+            let mut stmt = tx.prepare(&format!(
+                "INSERT INTO {} ({}) VALUES ({})",
+                quote_ident(table),
+                names.join(", "),
+                placeholders
+            ))?;
+            let (nrow, _) = self.shape();
+            for i_row in 0..nrow {
+                let row = self
+                    .data
+                    .iter()
+                    .map(|col| to_sql_value(col.data.get_value(i_row)))
+                    .collect::<Result<Vec<SqlValue>>>()?;
+                stmt.execute(params_from_iter(row))?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn from_sqlite_query(conn: &Connection, sql: &str) -> Result<DataFrame> {
+        let (sql_columns, rows) = query_rows(conn, sql)?;
+
+        let data = sql_columns
+            .into_iter()
+            .enumerate()
+            .map(|(i, (name, decl))| {
+                let dtype = match decl {
+                    Some(decl) => dtype_from_decl(&decl),
+                    None => rows
+                        .iter()
+                        .map(|row| &row[i])
+                        .find(|v| **v != SqlValue::Null)
+                        .map_or(Dtype::ColStringNullable, dtype_from_value),
+                };
+                Column::new(Some(name), dtype)
+            })
+            .collect();
+
+        let mut df = DataFrame::from_columns(data)?;
+        df.push_sql_rows(&rows)?;
+        Ok(df)
+    }
+
+    //as from_sqlite_query, but the result columns are read by position into the schema
+    pub fn from_sqlite_query_as<S: ToSchema + ?Sized>(
+        conn: &Connection,
+        sql: &str,
+        schema: &S,
+    ) -> Result<DataFrame> {
+        let mut df = DataFrame::new(schema)?;
+        let (sql_columns, rows) = query_rows(conn, sql)?;
+        if sql_columns.len() != df.data.len() {
+            Err(DFError {
+                error_msg: format!(
+                    "query gives {} columns, the schema has {}",
+                    sql_columns.len(),
+                    df.data.len()
+                ),
+                sub_errors: Vec::new(),
+            })?;
+        }
+        df.push_sql_rows(&rows)?;
+        Ok(df)
+    }
+
+    fn push_sql_rows(&mut self, rows: &[Vec<SqlValue>]) -> Result<()> {
+        for (i, column) in self.data.iter_mut().enumerate() {
+            let cells: Vec<&SqlValue> = rows.iter().map(|row| &row[i]).collect();
+            push_sql_column(column, &cells)?;
+        }
+        Ok(())
+    }
+}
+
+//names and declared types of the result columns, and all rows
+type SqlColumns = Vec<(String, Option<String>)>;
+fn query_rows(conn: &Connection, sql: &str) -> Result<(SqlColumns, Vec<Vec<SqlValue>>)> {
+    let mut stmt = conn.prepare(sql)?;
+    let sql_columns: SqlColumns = stmt
+        .columns()
+        .iter()
+        .map(|c| (c.name().to_string(), c.decl_type().map(String::from)))
+        .collect();
+    let ncol = sql_columns.len();
+
+    let rows: Vec<Vec<SqlValue>> = stmt
+        .query_map([], |row| (0..ncol).map(|i| row.get(i)).collect())?
+        .collect::<std::result::Result<_, _>>()?;
+    Ok((sql_columns, rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataframe::{column, csv_read_str};
+
+    #[test]
+    fn sqlite_round_trip() {
+        let mycsvstr = "1,2.5,3,four\n10,,30,fourty";
+        let myschema = "a:int,b:doubleNullable,c:intNullable,d:string";
+        let df = csv_read_str(mycsvstr, myschema).unwrap();
+
+        let mut conn = Connection::open_in_memory().unwrap();
+        df.to_sqlite(&mut conn, "my table").unwrap();
+
+        let df2 = DataFrame::from_sqlite_query(&conn, "SELECT * FROM \"my table\"").unwrap();
+        assert_eq!(df2.shape(), (2, 4));
+        assert_eq!(df2.data[0].name, Some(String::from("a")));
+        assert_eq!(df2.data[0].dtype(), Dtype::ColI64Nullable);
+
+        let b_col = df2.data[1].get::<column::ColF64Nullable>().unwrap();
+        assert_eq!(b_col.iter().collect::<Vec<_>>(), vec![Some(2.5), None]);
+        assert_eq!(df2.data[1].data.null_count(), 1);
+
+        let d_col = df2.data[3].get::<column::ColStringNullable>().unwrap();
+        assert_eq!(d_col.get(0), Some("four"));
+        assert_eq!(d_col.get(1), Some("fourty"));
+    }
+
+    #[test]
+    fn sqlite_u64_range() {
+        let df = csv_read_str("9223372036854775807,1", "a:u64,b:u64Nullable").unwrap();
+        let mut conn = Connection::open_in_memory().unwrap();
+        df.to_sqlite(&mut conn, "t").unwrap();
+        let df2 = DataFrame::from_sqlite_query(&conn, "SELECT * FROM t").unwrap();
+        assert_eq!(df2.data[0].data.get_value(0), Value::Int(i64::MAX));
+
+        //larger values are an error instead of a null, and no table is left behind
+        let df = csv_read_str("18446744073709551615,", "a:u64,b:u64Nullable").unwrap();
+        assert!(df.to_sqlite(&mut conn, "big").is_err());
+        assert!(DataFrame::from_sqlite_query(&conn, "SELECT * FROM big").is_err());
+    }
+
+    #[test]
+    fn sqlite_not_null_for_non_nullable_dtypes() {
+        let df = csv_read_str("1,x", "a:int,b:string").unwrap();
+        let mut conn = Connection::open_in_memory().unwrap();
+        df.to_sqlite(&mut conn, "t").unwrap();
+
+        let res = conn.execute("INSERT INTO t (a, b) VALUES (NULL, 'y')", []);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn sqlite_round_trip_temporal() {
+        let mycsvstr = "2021-03-04,2021-03-04 05:06:07.5\n,";
+        let myschema = "d:dateNullable,ts:datetimeNullable";
+        let df = csv_read_str(mycsvstr, myschema).unwrap();
+
+        let mut conn = Connection::open_in_memory().unwrap();
+        df.to_sqlite(&mut conn, "t").unwrap();
+        let df2 = DataFrame::from_sqlite_query(&conn, "SELECT * FROM t").unwrap();
+
+        assert_eq!(df2.data[0].dtype(), Dtype::ColDateNullable);
+        assert_eq!(df2.data[0].data.get_value(0), df.data[0].data.get_value(0));
+        assert_eq!(df2.data[0].data.get_value(1), Value::Null);
+        assert_eq!(df2.data[1].data.get_value(0), df.data[1].data.get_value(0));
+    }
+
+    #[test]
+    fn sqlite_round_trip_decimal() {
+        let df = csv_read_str("12.3400\n99999999999999.9999", "amount:decimal[18,4]").unwrap();
+        let mut conn = Connection::open_in_memory().unwrap();
+        df.to_sqlite(&mut conn, "t").unwrap();
+        let df2 = DataFrame::from_sqlite_query(&conn, "SELECT * FROM t").unwrap();
+
+        assert_eq!(df2.data[0].dtype(), Dtype::ColDecimalNullable(18, 4));
+        assert_eq!(
+            df2.data[0].data.to_string(),
+            "12.3400, 99999999999999.9999, "
+        );
+    }
+
+    #[test]
+    fn sqlite_round_trip_binary() {
+        let df = csv_read_str("1,00ff\n2,", "id:int,h:binaryNullable").unwrap();
+        let mut conn = Connection::open_in_memory().unwrap();
+        df.to_sqlite(&mut conn, "t").unwrap();
+        let df2 = DataFrame::from_sqlite_query(&conn, "SELECT * FROM t").unwrap();
+
+        assert_eq!(df2.data[1].dtype(), Dtype::ColBinaryNullable);
+        assert_eq!(df2.data[1].data.get_value(0), Value::Binary(&[0x00, 0xff]));
+        assert_eq!(df2.data[1].data.get_value(1), Value::Null);
+    }
+
+    #[test]
+    fn sqlite_query_with_schema() {
+        let df = csv_read_str("1,00ff,2021-03-04", "id:int,h:binary,d:string").unwrap();
+        let mut conn = Connection::open_in_memory().unwrap();
+        df.to_sqlite(&mut conn, "t").unwrap();
+
+        let schema = crate::dataframe::Schema::parse("id:u32,h:binary[base64],d:date").unwrap();
+        let df2 = DataFrame::from_sqlite_query_as(&conn, "SELECT * FROM t", &schema).unwrap();
+        assert_eq!(df2.schema(), schema);
+        assert_eq!(df2.data[1].data.to_string(), "AP8=, ");
+        assert_eq!(df2.data[2].data.get_value(0), Value::Date(18690));
+
+        let res = DataFrame::from_sqlite_query_as(&conn, "SELECT id FROM t", "id:int,h:binary");
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn sqlite_null_text() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE t (s TEXT); INSERT INTO t VALUES ('x'), (NULL);")
+            .unwrap();
+
+        let df = DataFrame::from_sqlite_query(&conn, "SELECT * FROM t").unwrap();
+        assert_eq!(df.data[0].dtype(), Dtype::ColStringNullable);
+        assert_eq!(df.data[0].data.get_value(1), Value::Null);
+
+        //a non-nullable schema needs a default for NULL
+        assert!(DataFrame::from_sqlite_query_as(&conn, "SELECT * FROM t", "s:string").is_err());
+        let df = DataFrame::from_sqlite_query_as(&conn, "SELECT * FROM t", "s:string[default=-]")
+            .unwrap();
+        assert_eq!(df.data[0].data.get_value(1), Value::Str("-"));
+    }
+
+    #[test]
+    fn sqlite_typed_values() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE t (s TEXT); INSERT INTO t VALUES (''), (x'6869');")
+            .unwrap();
+
+        //an empty TEXT is an empty string, not NULL, and utf-8 blobs are their text
+        let df = DataFrame::from_sqlite_query(&conn, "SELECT * FROM t").unwrap();
+        assert_eq!(df.data[0].data.get_value(0), Value::Str(""));
+        assert_eq!(df.data[0].data.get_value(1), Value::Str("hi"));
+        conn.execute("INSERT INTO t VALUES (x'ff')", []).unwrap();
+        assert!(DataFrame::from_sqlite_query(&conn, "SELECT * FROM t").is_err());
+
+        //bools are written as 0/1 and read back whatever their tokens
+        let schema = "b:bool[true=Y;false=N],n:boolNullable[true=Y;false=N]";
+        let df = csv_read_str("Y,N\nN,", schema).unwrap();
+        let mut conn = Connection::open_in_memory().unwrap();
+        df.to_sqlite(&mut conn, "b").unwrap();
+        let df2 = DataFrame::from_sqlite_query_as(&conn, "SELECT * FROM b", schema).unwrap();
+        assert_eq!(df2.data[0].data.get_value(0), Value::Bool(true));
+        assert_eq!(df2.data[0].data.get_value(1), Value::Bool(false));
+        assert_eq!(df2.data[1].data.get_value(0), Value::Bool(false));
+        assert_eq!(df2.data[1].data.get_value(1), Value::Null);
+    }
+
+    #[test]
+    fn sqlite_query_without_declared_type() {
+        let conn = Connection::open_in_memory().unwrap();
+        let df = DataFrame::from_sqlite_query(&conn, "SELECT 1 + 1 AS two, 0.5 AS half").unwrap();
+        assert_eq!(df.data[0].dtype(), Dtype::ColI64Nullable);
+        assert_eq!(df.data[1].dtype(), Dtype::ColF64Nullable);
+        assert_eq!(df.data[0].data.get_value(0), Value::Int(2));
+    }
+}
@@ -0,0 +1,836 @@
+use super::column::{
+    extend_source, nullable_values, ColError, ColErrorcode, Dtype, Value, VectorData,
+};
+use crate::bitmap::Bitmap;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::error;
+use std::fmt;
+
+type Result<T> = std::result::Result<T, Box<dyn error::Error>>;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f";
+//tried in order when a datetime column has no declared format
+const DATETIME_FALLBACK_FORMATS: &[&str] = &["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"];
+
+//TimeUnit is the precision of datetime and duration ticks
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum TimeUnit {
+    Nanoseconds,
+    Microseconds,
+    Milliseconds,
+}
+
+impl TimeUnit {
+    pub fn from_str_to_res(s: &str) -> Result<TimeUnit> {
+        match s.trim() {
+            "ns" => Ok(TimeUnit::Nanoseconds),
+            "us" => Ok(TimeUnit::Microseconds),
+            "ms" => Ok(TimeUnit::Milliseconds),
+            _ => Err(Box::new(ColError {
+                errorcode: ColErrorcode::SchemaSyntax,
+                error_msg: format!("unknown time unit \"{}\", expected ns, us or ms", s),
+            })),
+        }
+    }
+
+    pub fn ticks_per_second(&self) -> i64 {
+        match self {
+            TimeUnit::Nanoseconds => 1_000_000_000,
+            TimeUnit::Microseconds => 1_000_000,
+            TimeUnit::Milliseconds => 1_000,
+        }
+    }
+
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            TimeUnit::Nanoseconds => "ns",
+            TimeUnit::Microseconds => "us",
+            TimeUnit::Milliseconds => "ms",
+        }
+    }
+}
+
+//TemporalArgs holds what the schema brackets of a temporal column declared,
+//e.g. datetime[%d.%m.%Y %H:%M;unit=ms;tz=+01:00]
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct TemporalArgs {
+    pub format: Option<String>,
+    pub unit: Option<TimeUnit>,
+    pub tz: Option<String>,
+}
+
+impl TemporalArgs {
+    //items are separated by ;, unit= and tz= are options and anything else is the format
+    pub fn parse(args: &str) -> Result<TemporalArgs> {
+        let mut out = TemporalArgs::default();
+        for item in args.split(';').filter(|x| !x.trim().is_empty()) {
+            if let Some(unit) = item.trim().strip_prefix("unit=") {
+                out.unit = Some(TimeUnit::from_str_to_res(unit)?);
+            } else if let Some(tz) = item.trim().strip_prefix("tz=") {
+                parse_tz(tz)?;
+                out.tz = Some(tz.trim().to_string());
+            } else {
+                out.format = Some(item.to_string());
+            }
+        }
+        Ok(out)
+    }
+}
+
+//timezones are fixed offsets like +02:00, or UTC
+fn parse_tz(tz: &str) -> Result<FixedOffset> {
+    let tz = tz.trim();
+    if tz.eq_ignore_ascii_case("utc") || tz == "Z" {
+        return Ok(FixedOffset::east_opt(0).unwrap());
+    }
+    let err = || ColError {
+        errorcode: ColErrorcode::SchemaSyntax,
+        error_msg: format!("timezone \"{}\" is not UTC or an offset like +02:00", tz),
+    };
+    let sign = match tz.chars().next() {
+        Some('+') => 1,
+        Some('-') => -1,
+        _ => return Err(Box::new(err())),
+    };
+    let (h, m) = tz[1..].split_once(':').ok_or_else(err)?;
+    let secs =
+        h.parse::<i32>().map_err(|_| err())? * 3600 + m.parse::<i32>().map_err(|_| err())? * 60;
+    Ok(FixedOffset::east_opt(sign * secs).ok_or_else(err)?)
+}
+
+fn parse_value_err(x: &str, what: &str) -> Box<dyn error::Error> {
+    Box::new(ColError {
+        errorcode: ColErrorcode::ParseValue,
+        error_msg: format!("cannot parse \"{}\" as {}", x, what),
+    })
+}
+
+fn range_err(x: i64, what: &str) -> Box<dyn error::Error> {
+    Box::new(ColError {
+        errorcode: ColErrorcode::ParseValue,
+        error_msg: format!("{} is out of the {} range", x, what),
+    })
+}
+
+//cells outside the calendar range of chrono are shown as their raw day or tick count
+pub(crate) fn or_raw(text: Result<String>, raw: i64) -> String {
+    text.unwrap_or_else(|_| raw.to_string())
+}
+
+fn epoch() -> NaiveDate {
+    NaiveDate::from_ymd_opt(1970, 1, 1).unwrap()
+}
+
+pub fn parse_date(x: &str, format: Option<&str>) -> Result<i32> {
+    let date = NaiveDate::parse_from_str(x.trim(), format.unwrap_or(DATE_FORMAT))
+        .map_err(|_| parse_value_err(x, "date"))?;
+    Ok((date - epoch()).num_days() as i32)
+}
+
+pub fn format_date(days: i32, format: Option<&str>) -> Result<String> {
+    let date = epoch()
+        .checked_add_signed(chrono::Duration::days(days as i64))
+        .ok_or_else(|| range_err(days as i64, "date"))?;
+    Ok(date.format(format.unwrap_or(DATE_FORMAT)).to_string())
+}
+
+fn naive_to_ticks(dt: NaiveDateTime, unit: TimeUnit) -> Option<i64> {
+    let utc = dt.and_utc();
+    match unit {
+        TimeUnit::Nanoseconds => utc.timestamp_nanos_opt(),
+        TimeUnit::Microseconds => Some(utc.timestamp_micros()),
+        TimeUnit::Milliseconds => Some(utc.timestamp_millis()),
+    }
+}
+
+fn ticks_to_utc(ticks: i64, unit: TimeUnit) -> Result<DateTime<Utc>> {
+    let per_sec = unit.ticks_per_second();
+    let nanos = ticks.rem_euclid(per_sec) * (1_000_000_000 / per_sec);
+    Utc.timestamp_opt(ticks.div_euclid(per_sec), nanos as u32)
+        .single()
+        .ok_or_else(|| range_err(ticks, "datetime"))
+}
+
+//parse a datetime, text without offset is read in tz (UTC if none)
+pub fn parse_datetime(
+    x: &str,
+    format: Option<&str>,
+    unit: TimeUnit,
+    tz: Option<&str>,
+) -> Result<i64> {
+    let x = x.trim();
+    let offset = parse_tz(tz.unwrap_or("UTC"))?;
+    let formats: Vec<&str> = match format {
+        Some(f) => vec![f],
+        None => DATETIME_FALLBACK_FORMATS.to_vec(),
+    };
+
+    let mut naive_utc = None;
+    for f in formats.iter() {
+        if let Ok(dt) = DateTime::parse_from_str(x, f) {
+            naive_utc = Some(dt.naive_utc());
+        } else if let Ok(dt) = NaiveDateTime::parse_from_str(x, f) {
+            naive_utc = offset
+                .from_local_datetime(&dt)
+                .single()
+                .map(|d| d.naive_utc());
+        } else if let Ok(d) = NaiveDate::parse_from_str(x, f) {
+            let dt = d.and_hms_opt(0, 0, 0).unwrap();
+            naive_utc = offset
+                .from_local_datetime(&dt)
+                .single()
+                .map(|d| d.naive_utc());
+        }
+        if naive_utc.is_some() {
+            break;
+        }
+    }
+    if naive_utc.is_none() && format.is_none() {
+        naive_utc = DateTime::parse_from_rfc3339(x).ok().map(|d| d.naive_utc());
+    }
+
+    naive_utc
+        .and_then(|dt| naive_to_ticks(dt, unit))
+        .ok_or_else(|| parse_value_err(x, "datetime"))
+}
+
+pub fn format_datetime(
+    ticks: i64,
+    format: Option<&str>,
+    unit: TimeUnit,
+    tz: Option<&str>,
+) -> Result<String> {
+    let offset = parse_tz(tz.unwrap_or("UTC"))?;
+    Ok(ticks_to_utc(ticks, unit)?
+        .with_timezone(&offset)
+        .format(format.unwrap_or(DATETIME_FORMAT))
+        .to_string())
+}
+
+//durations are a plain tick count in the column unit, or parts like 1d2h30m15s500ms
+pub fn parse_duration(x: &str, unit: TimeUnit) -> Result<i64> {
+    let x = x.trim();
+    if let Ok(ticks) = x.parse::<i64>() {
+        return Ok(ticks);
+    }
+    let (sign, body) = match x.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, x),
+    };
+    if body.is_empty() {
+        return Err(parse_value_err(x, "duration"));
+    }
+
+    let per_sec = unit.ticks_per_second() as i128;
+    let mut total: i128 = 0;
+    let mut rest = body;
+    while !rest.is_empty() {
+        let n_digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+        let n_alpha = rest[n_digits..]
+            .chars()
+            .take_while(|c| c.is_ascii_alphabetic())
+            .count();
+        if n_digits == 0 || n_alpha == 0 {
+            return Err(parse_value_err(x, "duration"));
+        }
+        let n: i128 = rest[..n_digits]
+            .parse()
+            .map_err(|_| parse_value_err(x, "duration"))?;
+        let (mul, div) = match &rest[n_digits..n_digits + n_alpha] {
+            "d" => (86400 * per_sec, 1),
+            "h" => (3600 * per_sec, 1),
+            "m" => (60 * per_sec, 1),
+            "s" => (per_sec, 1),
+            "ms" => (per_sec, 1_000),
+            "us" => (per_sec, 1_000_000),
+            "ns" => (per_sec, 1_000_000_000),
+            _ => return Err(parse_value_err(x, "duration")),
+        };
+        total = n
+            .checked_mul(mul)
+            .and_then(|t| total.checked_add(t / div))
+            .ok_or_else(|| parse_value_err(x, "duration"))?;
+        rest = &rest[n_digits + n_alpha..];
+    }
+    i64::try_from(sign * total).map_err(|_| parse_value_err(x, "duration"))
+}
+
+pub fn format_duration(ticks: i64, unit: TimeUnit) -> String {
+    if ticks == 0 {
+        return String::from("0s");
+    }
+    let per_sec = unit.ticks_per_second() as u64;
+    let mut rest = ticks.unsigned_abs();
+    let mut out = String::from(if ticks < 0 { "-" } else { "" });
+    let parts: [(&str, u64); 4] = [
+        ("d", 86400 * per_sec),
+        ("h", 3600 * per_sec),
+        ("m", 60 * per_sec),
+        ("s", per_sec),
+    ];
+    for (suffix, size) in parts.iter() {
+        if rest >= *size {
+            out += &format!("{}{}", rest / size, suffix);
+            rest %= size;
+        }
+    }
+    if rest > 0 {
+        out += &format!("{}{}", rest, unit.suffix());
+    }
+    out
+}
+
+//date as days since epoch 1970-01-01
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct ColDate {
+    pub data: Vec<i32>,
+    pub format: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct ColDateNullable {
+    pub data: Vec<i32>,
+    pub validity: Bitmap,
+    pub format: Option<String>,
+}
+
+//datetime as ticks of unit since epoch in UTC, tz is used for parsing and display
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColDatetime {
+    pub data: Vec<i64>,
+    pub unit: TimeUnit,
+    pub format: Option<String>,
+    pub tz: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColDatetimeNullable {
+    pub data: Vec<i64>,
+    pub validity: Bitmap,
+    pub unit: TimeUnit,
+    pub format: Option<String>,
+    pub tz: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColDuration {
+    pub data: Vec<i64>,
+    pub unit: TimeUnit,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColDurationNullable {
+    pub data: Vec<i64>,
+    pub validity: Bitmap,
+    pub unit: TimeUnit,
+}
+
+nullable_values!(ColDateNullable, i32);
+nullable_values!(ColDatetimeNullable, i64);
+nullable_values!(ColDurationNullable, i64);
+
+impl ColDate {
+    pub fn new(args: TemporalArgs) -> ColDate {
+        ColDate {
+            data: Vec::new(),
+            format: args.format,
+        }
+    }
+}
+
+impl ColDateNullable {
+    pub fn new(args: TemporalArgs) -> ColDateNullable {
+        ColDateNullable {
+            data: Vec::new(),
+            validity: Bitmap::new(),
+            format: args.format,
+        }
+    }
+}
+
+impl ColDatetime {
+    pub fn new(unit: TimeUnit, args: TemporalArgs) -> ColDatetime {
+        ColDatetime {
+            data: Vec::new(),
+            unit,
+            format: args.format,
+            tz: args.tz,
+        }
+    }
+}
+
+impl ColDatetimeNullable {
+    pub fn new(unit: TimeUnit, args: TemporalArgs) -> ColDatetimeNullable {
+        ColDatetimeNullable {
+            data: Vec::new(),
+            validity: Bitmap::new(),
+            unit,
+            format: args.format,
+            tz: args.tz,
+        }
+    }
+}
+
+impl ColDuration {
+    pub fn new(unit: TimeUnit) -> ColDuration {
+        ColDuration {
+            data: Vec::new(),
+            unit,
+        }
+    }
+}
+
+impl ColDurationNullable {
+    pub fn new(unit: TimeUnit) -> ColDurationNullable {
+        ColDurationNullable {
+            data: Vec::new(),
+            validity: Bitmap::new(),
+            unit,
+        }
+    }
+}
+
+//bracket arguments of a temporal dtype, the unit only if it is not the default
+fn temporal_type_args(
+    format: &Option<String>,
+    unit: Option<TimeUnit>,
+    tz: &Option<String>,
+) -> String {
+    let mut items: Vec<String> = format.iter().cloned().collect();
+    if let Some(unit) = unit.filter(|u| *u != TimeUnit::Microseconds) {
+        items.push(format!("unit={}", unit.suffix()));
+    }
+    if let Some(tz) = tz {
+        items.push(format!("tz={}", tz));
+    }
+    items.join(";")
+}
+
+fn join_cells<I: Iterator<Item = String>>(cells: I) -> String {
+    cells.fold(String::new(), |a, b| a + &b[..] + ", ")
+}
+
+fn na_or<T, F: Fn(T) -> String>(x: Option<T>, f: F) -> String {
+    match x {
+        Some(y) => f(y),
+        None => String::from("NA"),
+    }
+}
+
+#[typetag::serde]
+impl VectorData for ColDate {
+    fn push_from_str(&mut self, x: &str) -> Result<()> {
+        self.data.push(parse_date(x, self.format.as_deref())?);
+        Ok(())
+    }
+    fn to_string(&self) -> String {
+        join_cells(
+            self.data
+                .iter()
+                .map(|x| or_raw(format_date(*x, self.format.as_deref()), *x as i64)),
+        )
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+    }
+    fn truncate(&mut self, len: usize) {
+        self.data.truncate(len);
+    }
+    fn boxed_clone(&self) -> Box<dyn VectorData> {
+        Box::new(self.clone())
+    }
+    fn dtype(&self) -> Dtype {
+        Dtype::ColDate
+    }
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+    fn get_value(&self, idx: usize) -> Value<'_> {
+        Value::Date(self.data[idx])
+    }
+    fn take(&self, indices: &[usize]) -> Box<dyn VectorData> {
+        Box::new(ColDate {
+            data: indices.iter().map(|i| self.data[*i]).collect(),
+            format: self.format.clone(),
+        })
+    }
+    fn type_args(&self) -> String {
+        temporal_type_args(&self.format, None, &None)
+    }
+    fn extend(&mut self, other: &dyn VectorData) -> Result<()> {
+        let other: &Self = extend_source(other, self.dtype())?;
+        self.data.extend_from_slice(&other.data);
+        Ok(())
+    }
+}
+
+#[typetag::serde]
+impl VectorData for ColDateNullable {
+    fn push_from_str(&mut self, x: &str) -> Result<()> {
+        self.push(parse_date(x, self.format.as_deref()).ok());
+        Ok(())
+    }
+    fn to_string(&self) -> String {
+        join_cells(self.iter().map(|x| {
+            na_or(x, |y| {
+                or_raw(format_date(y, self.format.as_deref()), y as i64)
+            })
+        }))
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+        self.validity.reserve(additional);
+    }
+    fn truncate(&mut self, len: usize) {
+        self.data.truncate(len);
+        self.validity.truncate(len);
+    }
+    fn boxed_clone(&self) -> Box<dyn VectorData> {
+        Box::new(self.clone())
+    }
+    fn dtype(&self) -> Dtype {
+        Dtype::ColDateNullable
+    }
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+    fn get_value(&self, idx: usize) -> Value<'_> {
+        self.get(idx).map_or(Value::Null, Value::Date)
+    }
+    fn take(&self, indices: &[usize]) -> Box<dyn VectorData> {
+        Box::new(ColDateNullable {
+            data: indices.iter().map(|i| self.data[*i]).collect(),
+            validity: self.validity.take(indices),
+            format: self.format.clone(),
+        })
+    }
+    fn validity(&self) -> Option<&Bitmap> {
+        Some(&self.validity)
+    }
+    fn type_args(&self) -> String {
+        temporal_type_args(&self.format, None, &None)
+    }
+    fn extend(&mut self, other: &dyn VectorData) -> Result<()> {
+        let other: &Self = extend_source(other, self.dtype())?;
+        self.data.extend_from_slice(&other.data);
+        self.validity.extend(&other.validity);
+        Ok(())
+    }
+}
+
+#[typetag::serde]
+impl VectorData for ColDatetime {
+    fn push_from_str(&mut self, x: &str) -> Result<()> {
+        let value = parse_datetime(x, self.format.as_deref(), self.unit, self.tz.as_deref())?;
+        self.data.push(value);
+        Ok(())
+    }
+    fn to_string(&self) -> String {
+        join_cells(self.data.iter().map(|x| {
+            let text = format_datetime(*x, self.format.as_deref(), self.unit, self.tz.as_deref());
+            or_raw(text, *x)
+        }))
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+    }
+    fn truncate(&mut self, len: usize) {
+        self.data.truncate(len);
+    }
+    fn boxed_clone(&self) -> Box<dyn VectorData> {
+        Box::new(self.clone())
+    }
+    fn dtype(&self) -> Dtype {
+        Dtype::ColDatetime(self.unit)
+    }
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+    fn get_value(&self, idx: usize) -> Value<'_> {
+        Value::Datetime(self.data[idx], self.unit)
+    }
+    fn take(&self, indices: &[usize]) -> Box<dyn VectorData> {
+        Box::new(ColDatetime {
+            data: indices.iter().map(|i| self.data[*i]).collect(),
+            unit: self.unit,
+            format: self.format.clone(),
+            tz: self.tz.clone(),
+        })
+    }
+    fn type_args(&self) -> String {
+        temporal_type_args(&self.format, Some(self.unit), &self.tz)
+    }
+    fn extend(&mut self, other: &dyn VectorData) -> Result<()> {
+        let other: &Self = extend_source(other, self.dtype())?;
+        self.data.extend_from_slice(&other.data);
+        Ok(())
+    }
+}
+
+#[typetag::serde]
+impl VectorData for ColDatetimeNullable {
+    fn push_from_str(&mut self, x: &str) -> Result<()> {
+        let value = parse_datetime(x, self.format.as_deref(), self.unit, self.tz.as_deref());
+        self.push(value.ok());
+        Ok(())
+    }
+    fn to_string(&self) -> String {
+        join_cells(self.iter().map(|x| {
+            na_or(x, |y| {
+                let text =
+                    format_datetime(y, self.format.as_deref(), self.unit, self.tz.as_deref());
+                or_raw(text, y)
+            })
+        }))
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+        self.validity.reserve(additional);
+    }
+    fn truncate(&mut self, len: usize) {
+        self.data.truncate(len);
+        self.validity.truncate(len);
+    }
+    fn boxed_clone(&self) -> Box<dyn VectorData> {
+        Box::new(self.clone())
+    }
+    fn dtype(&self) -> Dtype {
+        Dtype::ColDatetimeNullable(self.unit)
+    }
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+    fn get_value(&self, idx: usize) -> Value<'_> {
+        self.get(idx)
+            .map_or(Value::Null, |x| Value::Datetime(x, self.unit))
+    }
+    fn take(&self, indices: &[usize]) -> Box<dyn VectorData> {
+        Box::new(ColDatetimeNullable {
+            data: indices.iter().map(|i| self.data[*i]).collect(),
+            validity: self.validity.take(indices),
+            unit: self.unit,
+            format: self.format.clone(),
+            tz: self.tz.clone(),
+        })
+    }
+    fn validity(&self) -> Option<&Bitmap> {
+        Some(&self.validity)
+    }
+    fn type_args(&self) -> String {
+        temporal_type_args(&self.format, Some(self.unit), &self.tz)
+    }
+    fn extend(&mut self, other: &dyn VectorData) -> Result<()> {
+        let other: &Self = extend_source(other, self.dtype())?;
+        self.data.extend_from_slice(&other.data);
+        self.validity.extend(&other.validity);
+        Ok(())
+    }
+}
+
+#[typetag::serde]
+impl VectorData for ColDuration {
+    fn push_from_str(&mut self, x: &str) -> Result<()> {
+        self.data.push(parse_duration(x, self.unit)?);
+        Ok(())
+    }
+    fn to_string(&self) -> String {
+        join_cells(self.data.iter().map(|x| format_duration(*x, self.unit)))
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+    }
+    fn truncate(&mut self, len: usize) {
+        self.data.truncate(len);
+    }
+    fn boxed_clone(&self) -> Box<dyn VectorData> {
+        Box::new(self.clone())
+    }
+    fn dtype(&self) -> Dtype {
+        Dtype::ColDuration(self.unit)
+    }
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+    fn get_value(&self, idx: usize) -> Value<'_> {
+        Value::Duration(self.data[idx], self.unit)
+    }
+    fn take(&self, indices: &[usize]) -> Box<dyn VectorData> {
+        Box::new(ColDuration {
+            data: indices.iter().map(|i| self.data[*i]).collect(),
+            unit: self.unit,
+        })
+    }
+    fn type_args(&self) -> String {
+        temporal_type_args(&None, Some(self.unit), &None)
+    }
+    fn extend(&mut self, other: &dyn VectorData) -> Result<()> {
+        let other: &Self = extend_source(other, self.dtype())?;
+        self.data.extend_from_slice(&other.data);
+        Ok(())
+    }
+}
+
+#[typetag::serde]
+impl VectorData for ColDurationNullable {
+    fn push_from_str(&mut self, x: &str) -> Result<()> {
+        self.push(parse_duration(x, self.unit).ok());
+        Ok(())
+    }
+    fn to_string(&self) -> String {
+        join_cells(
+            self.iter()
+                .map(|x| na_or(x, |y| format_duration(y, self.unit))),
+        )
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+        self.validity.reserve(additional);
+    }
+    fn truncate(&mut self, len: usize) {
+        self.data.truncate(len);
+        self.validity.truncate(len);
+    }
+    fn boxed_clone(&self) -> Box<dyn VectorData> {
+        Box::new(self.clone())
+    }
+    fn dtype(&self) -> Dtype {
+        Dtype::ColDurationNullable(self.unit)
+    }
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+    fn get_value(&self, idx: usize) -> Value<'_> {
+        self.get(idx)
+            .map_or(Value::Null, |x| Value::Duration(x, self.unit))
+    }
+    fn take(&self, indices: &[usize]) -> Box<dyn VectorData> {
+        Box::new(ColDurationNullable {
+            data: indices.iter().map(|i| self.data[*i]).collect(),
+            validity: self.validity.take(indices),
+            unit: self.unit,
+        })
+    }
+    fn validity(&self) -> Option<&Bitmap> {
+        Some(&self.validity)
+    }
+    fn type_args(&self) -> String {
+        temporal_type_args(&None, Some(self.unit), &None)
+    }
+    fn extend(&mut self, other: &dyn VectorData) -> Result<()> {
+        let other: &Self = extend_source(other, self.dtype())?;
+        self.data.extend_from_slice(&other.data);
+        self.validity.extend(&other.validity);
+        Ok(())
+    }
+}
+
+impl fmt::Display for TimeUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.suffix())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn date_round_trip() {
+        assert_eq!(parse_date("1970-01-02", None).unwrap(), 1);
+        assert_eq!(parse_date("31.12.1969", Some("%d.%m.%Y")).unwrap(), -1);
+        assert_eq!(format_date(1, Some("%d/%m/%Y")).unwrap(), "02/01/1970");
+        assert!(parse_date("1970-13-01", None).is_err());
+
+        //days beyond the calendar range are an error, columns show them raw
+        assert!(format_date(i32::MAX, None).is_err());
+        assert!(format_date(i32::MIN, None).is_err());
+        let col = ColDate {
+            data: vec![0, 2_000_000_000],
+            format: None,
+        };
+        assert_eq!(col.to_string(), "1970-01-01, 2000000000, ");
+    }
+
+    #[test]
+    fn datetime_units_and_tz() {
+        let us = parse_datetime("1970-01-01 00:00:01.5", None, TimeUnit::Microseconds, None);
+        assert_eq!(us.unwrap(), 1_500_000);
+
+        //local time in +01:00 is one hour earlier in UTC
+        let ms = parse_datetime(
+            "01.01.1970 01:00",
+            Some("%d.%m.%Y %H:%M"),
+            TimeUnit::Milliseconds,
+            Some("+01:00"),
+        );
+        assert_eq!(ms.unwrap(), 0);
+
+        //an explicit offset in the text wins over the column timezone
+        let ns = parse_datetime(
+            "1970-01-01T00:00:00+00:00",
+            None,
+            TimeUnit::Nanoseconds,
+            None,
+        );
+        assert_eq!(ns.unwrap(), 0);
+
+        let text = format_datetime(-1, None, TimeUnit::Milliseconds, None);
+        assert_eq!(text.unwrap(), "1969-12-31 23:59:59.999");
+        let text = format_datetime(0, Some("%H:%M %z"), TimeUnit::Milliseconds, Some("+02:00"));
+        assert_eq!(text.unwrap(), "02:00 +0200");
+
+        //all nanosecond ticks fit the calendar, millisecond extremes do not
+        assert!(format_datetime(i64::MIN, None, TimeUnit::Nanoseconds, None).is_ok());
+        assert!(format_datetime(i64::MAX, None, TimeUnit::Milliseconds, None).is_err());
+        assert!(format_datetime(i64::MIN, None, TimeUnit::Microseconds, None).is_err());
+    }
+
+    #[test]
+    fn duration_round_trip() {
+        let unit = TimeUnit::Milliseconds;
+        assert_eq!(parse_duration("1500", unit).unwrap(), 1500);
+        assert_eq!(parse_duration("1h30m", unit).unwrap(), 5_400_000);
+        assert_eq!(parse_duration("-2s250ms", unit).unwrap(), -2250);
+        assert!(parse_duration("2 weeks", unit).is_err());
+        assert!(parse_duration("99999999999999999999999999999999999d", unit).is_err());
+        assert!(parse_duration("999999999999999999999999999999999999999999d", unit).is_err());
+        assert!(parse_duration("106751991168d", unit).is_err());
+        assert_eq!(
+            parse_duration("106751991d", unit).unwrap(),
+            106751991 * 86_400_000
+        );
+        assert_eq!(format_duration(5_400_250, unit), "1h30m250ms");
+        assert_eq!(format_duration(-2250, unit), "-2s250ms");
+    }
+
+    #[test]
+    fn temporal_args() {
+        let args = TemporalArgs::parse("%d.%m.%Y %H:%M:%S;unit=ms;tz=+01:00").unwrap();
+        assert_eq!(args.format.as_deref(), Some("%d.%m.%Y %H:%M:%S"));
+        assert_eq!(args.unit, Some(TimeUnit::Milliseconds));
+        assert_eq!(args.tz.as_deref(), Some("+01:00"));
+        assert!(TemporalArgs::parse("unit=s").is_err());
+        assert!(TemporalArgs::parse("tz=Europe/Paris").is_err());
+    }
+}
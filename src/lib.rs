@@ -1,2 +1,3 @@
+pub mod bitmap;
 pub mod dataframe;
 pub mod stringpool;
\ No newline at end of file
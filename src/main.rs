@@ -1,11 +1,6 @@
-mod dataframe;
-pub mod stringpool;
-use bincode;
-use serde::{Deserialize, Serialize};
-use serde_json;
+use teddies::dataframe;
 
 fn main() {
-    use dataframe::column;
     let mystring = String::from("my.csv");
     let myschema = "a:int,b:doubleNullable,c:int,someothername:string";
     let df = dataframe::csv_read_file_iter2(&mystring, myschema).unwrap();
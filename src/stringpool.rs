@@ -65,6 +65,41 @@ impl StringPool {
     pub fn len(&self) -> usize {
         self.v.len()
     }
+
+    //keep the first len strings, the bytes of the dropped ones are freed from the last chunks
+    pub fn truncate(&mut self, len: usize) {
+        self.v.truncate(len);
+        match self.v.last() {
+            Some(t) => {
+                self.pool.truncate(t.i_chunk + 1);
+                self.pool[t.i_chunk].truncate(t.start + t.len);
+            }
+            None => {
+                self.pool.truncate(1);
+                self.pool[0].clear();
+            }
+        }
+    }
+
+    //append the strings of other. its chunks are copied whole and the tickets moved to them,
+    //only a pool fitting into the last chunk is added string by string
+    pub fn extend(&mut self, other: &StringPool) {
+        let other_bytes: usize = other.pool.iter().map(|c| c.len()).sum();
+        let last_len = self.pool[self.get_last_idx()].len();
+        if other_bytes + last_len <= POOL_STRING_SIZE {
+            (0..other.len()).for_each(|i| self.add_str(other.get_str(i)));
+            return;
+        }
+        if self.v.is_empty() {
+            self.pool.clear();
+        }
+        let base = self.pool.len();
+        self.pool.extend(other.pool.iter().cloned());
+        self.v.extend(other.v.iter().map(|t| StringTicket {
+            i_chunk: t.i_chunk + base,
+            ..t.clone()
+        }));
+    }
 }
 
 impl Default for StringPool {
@@ -135,6 +170,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn extend_by_chunks() {
+        let mut sp = StringPool::new();
+        sp.add_str("first");
+        let mut small = StringPool::new();
+        small.add_str("second");
+        sp.extend(&small);
+        assert_eq!(sp.pool.len(), 1);
+
+        let mut big = StringPool::new();
+        for i in 0..POOL_STRING_SIZE {
+            big.add_str(&format!("s{}", i));
+        }
+        sp.extend(&big);
+        assert_eq!(sp.pool.len(), 1 + big.pool.len());
+        assert_eq!(sp.len(), 2 + POOL_STRING_SIZE);
+        assert_eq!(sp.get_str(1), "second");
+        assert_eq!(sp.get_str(2 + 700), "s700");
+        sp.add_str("last");
+        assert_eq!(sp.get_str(sp.len() - 1), "last");
+
+        sp.truncate(2);
+        assert_eq!(sp.pool, vec![String::from("firstsecond")]);
+        sp.add_str("again");
+        assert_eq!(sp.get_str(2), "again");
+        sp.truncate(0);
+        assert_eq!((sp.len(), sp.pool.len()), (0, 1));
+
+        let mut empty = StringPool::new();
+        empty.extend(&big);
+        assert_eq!(empty.pool, big.pool);
+    }
+
     #[test]
     fn reserve_ps() {
         //make string pool with some chunks